@@ -17,7 +17,8 @@ export enum ClientMethod {
   GET_DOCUMENT_SIGNATURES = 'get_document_signatures',
   MARK_DOCUMENT_FULLY_SIGNED = 'mark_document_fully_signed',
   GET_CONTEXT_DETAILS = 'get_context_details',
-  ADD_PARTICIPANT = 'add_participant',
+  INVITE_PARTICIPANT = 'invite_participant',
+  ACCEPT_INVITATION = 'accept_invitation',
   MARK_PARTICIPANT_SIGNED = 'mark_participant_signed',
   SET_CONSENT = 'set_consent',
   HAS_CONSENTED = 'has_consented',
@@ -171,15 +172,19 @@ export interface ClientApi {
     agreementContextID?: string,
     agreementContextUserID?: string,
   ): ApiResponse<void>;
-  addParticipant(
+  inviteParticipant(
     contextId: string,
     userId: UserId,
     permission: PermissionLevel,
     agreementContextID?: string,
     agreementContextUserID?: string,
   ): ApiResponse<void>;
+  acceptInvitation(
+    contextId: string,
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): ApiResponse<void>;
   setConsent(
-    userId: UserId,
     documentId: string,
     agreementContextID?: string,
     agreementContextUserID?: string,
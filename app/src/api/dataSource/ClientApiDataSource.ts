@@ -19,6 +19,51 @@ import { DefaultContextService } from '../defaultContextService';
 
 const RequestConfig = { timeout: 30000 };
 
+/** Error returned by the MeroDocs contract, serialized as `{ code, details }` */
+interface MeroDocsError {
+  code: string;
+  details?: any;
+}
+
+function asMeroDocsError(value: any): MeroDocsError | null {
+  if (typeof value === 'string') {
+    try {
+      value = JSON.parse(value);
+    } catch {
+      return null;
+    }
+  }
+  return value && typeof value.code === 'string' ? value : null;
+}
+
+function describeMeroDocsError({ code, details }: MeroDocsError): string {
+  switch (code) {
+    case 'NOT_FOUND':
+      return `${details?.resource ?? 'Item'} not found`;
+    case 'ALREADY_EXISTS':
+      return `${details?.resource ?? 'Item'} already exists`;
+    case 'NOT_PARTICIPANT':
+      return 'You are not a participant of this agreement';
+    case 'PERMISSION_DENIED':
+      return `You do not have ${details?.required ?? 'the required'} permission for this action`;
+    case 'CONSENT_REQUIRED':
+      return 'Consent is required before signing this document';
+    case 'NOT_SIGNERS_TURN':
+      return 'It is not your turn to sign this document';
+    case 'INVALID_SIGNATURE':
+      return 'The signature could not be verified';
+    case 'DEADLINE_PASSED':
+      return 'The signing deadline has passed';
+    case 'CONTEXT_EXPIRED':
+      return 'This context has expired and no longer accepts changes';
+    default:
+      if (typeof details === 'string') return details;
+      return details === undefined
+        ? code
+        : `${code}: ${JSON.stringify(details)}`;
+  }
+}
+
 function getErrorMessage(error: any): string {
   if (
     error?.type === 'Uninitialized' ||
@@ -34,6 +79,10 @@ function getErrorMessage(error: any): string {
   ) {
     return 'Syncing state, Please wait and retry.';
   }
+  const contractError =
+    asMeroDocsError(error?.data) ??
+    asMeroDocsError(error?.error?.cause?.info?.data);
+  if (contractError) return describeMeroDocsError(contractError);
   if (typeof error === 'string') return error;
   if (error?.message) return error.message;
   if (error?.data) return JSON.stringify(error.data);
@@ -61,7 +110,6 @@ export class ClientApiDataSource implements ClientApi {
   }
 
   async setConsent(
-    userId: UserId,
     documentId: string,
     agreementContextID?: string,
     agreementContextUserID?: string,
@@ -79,7 +127,6 @@ export class ClientApiDataSource implements ClientApi {
         ...authConfig,
         method: ClientMethod.SET_CONSENT,
         argsJson: {
-          user_id: userId,
           document_id: documentId,
         },
       } as RpcQueryParams<any>);
@@ -189,7 +236,7 @@ export class ClientApiDataSource implements ClientApi {
     }
   }
 
-  async addParticipant(
+  async inviteParticipant(
     contextId: string,
     userId: UserId,
     permission: PermissionLevel,
@@ -208,11 +255,12 @@ export class ClientApiDataSource implements ClientApi {
 
       const response = await rpcClient.execute({
         ...authConfig,
-        method: ClientMethod.ADD_PARTICIPANT,
+        method: ClientMethod.INVITE_PARTICIPANT,
         argsJson: {
           context_id: contextId,
           user_id: userId,
           permission: permission,
+          expires_at: null,
         },
       } as RpcQueryParams<any>);
 
@@ -228,7 +276,7 @@ export class ClientApiDataSource implements ClientApi {
       try {
         const safeContextId = contextId.replace(/[^a-zA-Z0-9_-]/g, '_');
         if (contextId !== safeContextId) {
-          console.warn('Sanitized contextId for ICP addParticipant:', {
+          console.warn('Sanitized contextId for ICP inviteParticipant:', {
             original: contextId,
             sanitized: safeContextId,
           });
@@ -257,7 +305,55 @@ export class ClientApiDataSource implements ClientApi {
         error: null,
       };
     } catch (error: any) {
-      console.error('ClientApiDataSource: Error in addParticipant:', error);
+      console.error('ClientApiDataSource: Error in inviteParticipant:', error);
+      return {
+        data: undefined,
+        error: {
+          code: 500,
+          message: getErrorMessage(error),
+        },
+      };
+    }
+  }
+
+  async acceptInvitation(
+    contextId: string,
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): ApiResponse<void> {
+    try {
+      const authConfig =
+        agreementContextID && agreementContextUserID
+          ? getContextSpecificAuthConfig(
+              agreementContextID,
+              agreementContextUserID,
+            )
+          : getAuthConfig();
+
+      const response = await rpcClient.execute({
+        ...authConfig,
+        method: ClientMethod.ACCEPT_INVITATION,
+        argsJson: {
+          context_id: contextId,
+        },
+      } as RpcQueryParams<any>);
+
+      if (response?.error) {
+        return {
+          data: undefined,
+          error: {
+            code: response.error.code ?? 500,
+            message: getErrorMessage(response.error),
+          },
+        };
+      }
+
+      return {
+        data: undefined,
+        error: null,
+      };
+    } catch (error: any) {
+      console.error('ClientApiDataSource: Error in acceptInvitation:', error);
       return {
         data: undefined,
         error: {
@@ -417,7 +513,7 @@ export class ClientApiDataSource implements ClientApi {
         pdf_blob_id_str: string;
         file_size: number;
         new_hash: string;
-        signer_id: string;
+        signature: null;
       }> = {
         contextId: contextId,
         method: ClientMethod.SIGN_DOCUMENT,
@@ -427,7 +523,8 @@ export class ClientApiDataSource implements ClientApi {
           pdf_blob_id_str: pdfBlobIdStr,
           file_size: fileSize,
           new_hash: newHash,
-          signer_id: signerId,
+          // The browser cannot sign with the context identity key, so the approval is unsigned
+          signature: null,
         },
         executorPublicKey: signerId,
       };
@@ -439,7 +536,7 @@ export class ClientApiDataSource implements ClientApi {
           pdf_blob_id_str: string;
           file_size: number;
           new_hash: string;
-          signer_id: string;
+          signature: null;
         },
         void
       >(params, RequestConfig);
@@ -5,7 +5,6 @@ import { ClientApiDataSource } from '../api/dataSource/ClientApiDataSource';
 import { useTheme } from '../contexts/ThemeContext';
 interface ConsentModalProps {
   open: boolean;
-  userId: string;
   documentId: string;
   agreementContextID?: string;
   agreementContextUserID?: string;
@@ -99,7 +98,6 @@ const DisclosureText = () => (
 
 const ConsentModal: React.FC<ConsentModalProps> = ({
   open,
-  userId,
   documentId,
   agreementContextID,
   agreementContextUserID,
@@ -120,7 +118,6 @@ const ConsentModal: React.FC<ConsentModalProps> = ({
     setError(null);
 
     const resp = await api.setConsent(
-      userId,
       documentId,
       agreementContextID,
       agreementContextUserID,
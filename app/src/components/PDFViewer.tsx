@@ -1280,7 +1280,6 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
 
       <ConsentModal
         open={showConsentModal}
-        userId={agreementContextUserID || ''}
         documentId={documentId!}
         agreementContextID={agreementContextID}
         agreementContextUserID={agreementContextUserID}
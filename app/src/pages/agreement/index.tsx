@@ -656,7 +656,7 @@ const AgreementPage: React.FC = () => {
       setShowPayloadDialog(true);
       setShowInviteModal(false);
 
-      const addResp = await clientApiService.addParticipant(
+      const inviteResp = await clientApiService.inviteParticipant(
         currentContextId,
         inviteId.trim(),
         invitePermission,
@@ -664,9 +664,9 @@ const AgreementPage: React.FC = () => {
         agreementContextID || undefined,
         agreementContextUserID || undefined,
       );
-      if (addResp.error) {
+      if (inviteResp.error) {
         showNotification(
-          'Failed to add participant: ' + addResp.error.message,
+          'Failed to invite participant: ' + inviteResp.error.message,
           'error',
         );
       }
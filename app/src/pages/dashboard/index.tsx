@@ -253,6 +253,23 @@ export default function Dashboard() {
         return;
       }
 
+      setJoinProgress('Accepting invitation...');
+
+      const acceptResponse = await clientApiService.acceptInvitation(
+        contextId,
+        contextId,
+        memberPublicKey,
+      );
+
+      if (acceptResponse.error) {
+        console.error('Accept invitation error:', acceptResponse.error);
+        setError(
+          'Failed to accept invitation: ' + acceptResponse.error.message,
+        );
+        setJoining(false);
+        return;
+      }
+
       setJoinProgress('Finalizing...');
       setShowJoinModal(false);
       setInvitationPayload('');
@@ -4,9 +4,10 @@ use ic_cdk::{caller, query, update};
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
 use ic_stable_structures::storable::Bound;
 use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, Storable};
+use sha2::{Digest, Sha256};
 use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -43,6 +44,7 @@ enum Error {
     DocumentNotReady,
     ConsentRequired,
     ContextNotFound,
+    Expired,
 }
 
 #[derive(CandidType, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -77,6 +79,7 @@ struct ContextRecord {
     context_status: ContextStatus,
     metadata: ContextMetadata,
     created_at: u64,
+    admin_signs: Option<bool>, // `None` on contexts created before the flag existed, which it signs
 }
 
 impl Storable for ContextRecord {
@@ -120,6 +123,7 @@ struct DocumentRecord {
     current_signers: Vec<String>,
     document_status: DocumentStatus,
     metadata: DocumentMetadata,
+    deadline: Option<u64>, // Signing deadline in nanoseconds, like `time()`
 }
 
 impl Storable for DocumentRecord {
@@ -142,11 +146,40 @@ struct DocumentMetadata {
     created_at: u64,
 }
 
-#[derive(CandidType, Deserialize, Clone, PartialEq)]
+#[derive(CandidType, Deserialize, Clone, PartialEq, Debug)]
 enum DocumentStatus {
     Pending,
     PartiallySigned,
     FullySigned,
+    Draft,
+    Declined,
+    Voided,
+    Expired,
+    Archived,
+}
+
+impl DocumentStatus {
+    fn is_open_for_signing(&self) -> bool {
+        matches!(
+            self,
+            DocumentStatus::Pending | DocumentStatus::PartiallySigned
+        )
+    }
+
+    /// Document lifecycle state machine, shared with the logic crate
+    fn can_transition_to(&self, next: &DocumentStatus) -> bool {
+        use DocumentStatus::*;
+
+        matches!(
+            (self, next),
+            (Draft, Pending)
+                | (Pending, PartiallySigned | FullySigned)
+                | (PartiallySigned, PartiallySigned | FullySigned)
+                | (Pending | PartiallySigned, Declined | Expired)
+                | (Draft | Pending | PartiallySigned, Voided)
+                | (FullySigned | Declined | Voided | Expired, Archived)
+        )
+    }
 }
 
 #[derive(CandidType, Deserialize, Clone)]
@@ -160,6 +193,49 @@ struct AuditEntry {
     consent_given: Option<bool>,
     document_hash_after_action: Option<String>,
     metadata: Option<String>,
+    // Hash chain; both are `None` on entries recorded before chaining was introduced
+    previous_hash: Option<String>,
+    entry_hash: Option<String>,
+    consent_text_hash: Option<String>, // SHA-256 of the consent/disclosure text accepted
+}
+
+impl AuditEntry {
+    /// Hex SHA-256 over `previous_hash` and every recorded field, each length-prefixed
+    fn compute_hash(&self, previous_hash: Option<&str>) -> String {
+        fn field(hasher: &mut Sha256, value: Option<&[u8]>) {
+            match value {
+                Some(bytes) => {
+                    hasher.update([1]);
+                    hasher.update((bytes.len() as u64).to_le_bytes());
+                    hasher.update(bytes);
+                }
+                None => hasher.update([0]),
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        field(&mut hasher, previous_hash.map(str::as_bytes));
+        field(&mut hasher, Some(self.entry_id.as_bytes()));
+        field(&mut hasher, Some(self.user_id.as_bytes()));
+        field(&mut hasher, Some(self.action.as_str().as_bytes()));
+        field(&mut hasher, Some(&self.timestamp.to_le_bytes()));
+        field(&mut hasher, Some(self.context_id.as_bytes()));
+        field(&mut hasher, self.document_id.as_deref().map(str::as_bytes));
+        let consent_given = self.consent_given.map(|given| [u8::from(given)]);
+        field(&mut hasher, consent_given.as_ref().map(|byte| &byte[..]));
+        field(
+            &mut hasher,
+            self.document_hash_after_action
+                .as_deref()
+                .map(str::as_bytes),
+        );
+        field(&mut hasher, self.metadata.as_deref().map(str::as_bytes));
+        // Only hashed when present, so entries chained before the field existed still verify
+        if let Some(consent_text_hash) = &self.consent_text_hash {
+            field(&mut hasher, Some(consent_text_hash.as_bytes()));
+        }
+        hex::encode(hasher.finalize())
+    }
 }
 
 #[derive(CandidType, Deserialize, Clone, PartialEq)]
@@ -171,6 +247,32 @@ enum AuditAction {
     SignatureApplied,
     DocumentCompleted,
     ContextCompleted,
+    DocumentDeclined,
+    DocumentVoided,
+    DocumentArchived,
+    DocumentExpired,
+    ContextExpired,
+    ConsentWithdrawn,
+}
+
+impl AuditAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::ContextCreated => "ContextCreated",
+            AuditAction::ParticipantAdded => "ParticipantAdded",
+            AuditAction::DocumentUploaded => "DocumentUploaded",
+            AuditAction::ConsentGiven => "ConsentGiven",
+            AuditAction::SignatureApplied => "SignatureApplied",
+            AuditAction::DocumentCompleted => "DocumentCompleted",
+            AuditAction::ContextCompleted => "ContextCompleted",
+            AuditAction::DocumentDeclined => "DocumentDeclined",
+            AuditAction::DocumentVoided => "DocumentVoided",
+            AuditAction::DocumentArchived => "DocumentArchived",
+            AuditAction::DocumentExpired => "DocumentExpired",
+            AuditAction::ContextExpired => "ContextExpired",
+            AuditAction::ConsentWithdrawn => "ConsentWithdrawn",
+        }
+    }
 }
 
 #[derive(CandidType, Deserialize, Clone)]
@@ -185,10 +287,52 @@ impl AuditTrail {
         }
     }
 
-    fn add_entry(&mut self, entry: AuditEntry) {
+    /// Append an entry, linking it to the hash of the current head
+    fn add_entry(&mut self, mut entry: AuditEntry) {
+        let previous_hash = self.head_hash().map(str::to_string);
+        entry.entry_hash = Some(entry.compute_hash(previous_hash.as_deref()));
+        entry.previous_hash = previous_hash;
         self.entries.push(entry);
     }
 
+    fn head_hash(&self) -> Option<&str> {
+        self.entries
+            .last()
+            .and_then(|entry| entry.entry_hash.as_deref())
+    }
+
+    /// Recompute the chain. Entries recorded before chaining are only accepted as a prefix.
+    fn verify(&self) -> ChainVerification {
+        let mut verification = ChainVerification {
+            entries_checked: self.entries.len() as u64,
+            unchained_entries: 0,
+            head_hash: None,
+            first_broken_link: None,
+        };
+
+        let mut previous_hash: Option<&str> = None;
+        for (index, entry) in self.entries.iter().enumerate() {
+            let intact = match &entry.entry_hash {
+                None => {
+                    verification.unchained_entries += 1;
+                    previous_hash.is_none() && entry.previous_hash.is_none()
+                }
+                Some(entry_hash) => {
+                    entry.previous_hash.as_deref() == previous_hash
+                        && *entry_hash == entry.compute_hash(previous_hash)
+                }
+            };
+            if !intact {
+                verification.first_broken_link = Some(index as u64);
+                break;
+            }
+            previous_hash = entry.entry_hash.as_deref();
+        }
+
+        verification.head_hash = self.head_hash().map(str::to_string);
+        verification
+    }
+
     fn get_entries(&self) -> &Vec<AuditEntry> {
         &self.entries
     }
@@ -209,6 +353,27 @@ impl Storable for AuditTrail {
     };
 }
 
+#[derive(CandidType, Deserialize)]
+struct ChainVerification {
+    entries_checked: u64,
+    unchained_entries: u64,
+    head_hash: Option<String>,
+    first_broken_link: Option<u64>, // Index of the first entry that fails verification
+}
+
+#[derive(CandidType, Deserialize)]
+struct SweepReport {
+    expired_contexts: Vec<String>,
+    expired_documents: Vec<String>,
+}
+
+#[derive(CandidType, Deserialize)]
+struct ExpiringItem {
+    context_id: String,
+    document_id: Option<String>, // `None` for the context deadline itself
+    expires_at: u64,
+}
+
 // Request structs
 #[derive(CandidType, Deserialize)]
 struct CreateContextRequest {
@@ -218,6 +383,9 @@ struct CreateContextRequest {
     description: Option<String>,
     agreement_type: Option<String>,
     expires_at: Option<u64>,
+    // Whether the admin must sign every document too; `false` for a relay that administers the
+    // context on behalf of its participants. Defaults to `true`.
+    admin_signs: Option<bool>,
 }
 
 #[derive(CandidType, Deserialize)]
@@ -225,6 +393,7 @@ struct DocumentUploadRequest {
     context_id: String,
     document_id: String,
     document_hash: String,
+    deadline: Option<u64>,
 }
 
 #[derive(CandidType, Deserialize)]
@@ -277,6 +446,99 @@ fn validate_hash(hash: &str) -> Result<(), Error> {
     Ok(())
 }
 
+fn context_admin(context_id: &str) -> Option<String> {
+    CONTEXTS.with(|contexts| {
+        contexts
+            .borrow()
+            .get(&StorableString(context_id.to_string()))
+            .map(|context| context.admin_id)
+    })
+}
+
+/// Move a document along its lifecycle and record the actor and reason in the audit trail
+fn transition_document(
+    document_id: &str,
+    next: DocumentStatus,
+    action: AuditAction,
+    reason: Option<String>,
+    authorize: impl FnOnce(&DocumentRecord, &str) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let user_id = caller().to_string();
+    let doc_key = StorableString(document_id.to_string());
+
+    DOCUMENTS.with(|documents| {
+        let mut documents = documents.borrow_mut();
+        let mut document = documents.get(&doc_key).ok_or(Error::NotFound)?;
+        authorize(&document, &user_id)?;
+
+        if !document.document_status.can_transition_to(&next) {
+            return Err(Error::UpdateConflict(format!(
+                "Cannot move a document from {:?} to {:?}.",
+                document.document_status, next
+            )));
+        }
+
+        document.document_status = next;
+        let context_id = document.context_id.clone();
+        let document_hash = document
+            .final_hash
+            .clone()
+            .unwrap_or_else(|| document.original_hash.clone());
+        documents.insert(doc_key, document);
+
+        let audit_entry = AuditEntry {
+            entry_id: generate_audit_id(),
+            user_id,
+            action,
+            timestamp: time(),
+            context_id: context_id.clone(),
+            document_id: Some(document_id.to_string()),
+            consent_given: None,
+            document_hash_after_action: Some(document_hash),
+            metadata: reason,
+            previous_hash: None,
+            entry_hash: None,
+            consent_text_hash: None,
+        };
+        add_audit_entry(&context_id, audit_entry);
+        Ok(())
+    })
+}
+
+/// Earlier of the document's own deadline and its context's `expires_at`
+fn effective_deadline(document: &DocumentRecord) -> Option<u64> {
+    let context_deadline = CONTEXTS.with(|contexts| {
+        contexts
+            .borrow()
+            .get(&StorableString(document.context_id.clone()))
+            .and_then(|context| context.metadata.expires_at)
+    });
+
+    match (document.deadline, context_deadline) {
+        (Some(document), Some(context)) => Some(document.min(context)),
+        (document, context) => document.or(context),
+    }
+}
+
+fn ensure_not_expired(document: &DocumentRecord) -> Result<(), Error> {
+    let context_expired = CONTEXTS.with(|contexts| {
+        contexts
+            .borrow()
+            .get(&StorableString(document.context_id.clone()))
+            .map_or(false, |context| {
+                context.context_status == ContextStatus::Expired
+            })
+    });
+
+    if context_expired
+        || document.document_status == DocumentStatus::Expired
+        || effective_deadline(document).map_or(false, |deadline| time() >= deadline)
+    {
+        return Err(Error::Expired);
+    }
+    Ok(())
+}
+
 fn generate_audit_id() -> String {
     format!("audit_{}", time())
 }
@@ -291,6 +553,17 @@ fn add_audit_entry(context_id: &str, entry: AuditEntry) {
     });
 }
 
+/// Principals that must sign every document of a context before it is fully signed
+fn required_signers(context: &ContextRecord) -> Vec<String> {
+    let mut required_signers = context.participants.clone();
+    if context.admin_signs.unwrap_or(true) {
+        required_signers.push(context.admin_id.clone());
+    }
+    required_signers.sort_unstable();
+    required_signers.dedup();
+    required_signers
+}
+
 fn is_context_participant(context_id: &str, user_id: &str) -> bool {
     let key = StorableString(context_id.to_string());
     CONTEXTS.with(|contexts| {
@@ -300,19 +573,31 @@ fn is_context_participant(context_id: &str, user_id: &str) -> bool {
     })
 }
 
+fn is_consent_decision(entry: &AuditEntry) -> bool {
+    matches!(
+        entry.action,
+        AuditAction::ConsentGiven | AuditAction::ConsentWithdrawn
+    )
+}
+
+/// Whether the user's latest consent decision for the document is a consent
 fn has_user_given_consent(context_id: &str, user_id: &str, document_id: &str) -> bool {
     let key = StorableString(context_id.to_string());
     AUDIT_TRAIL.with(|trail| {
         trail.borrow().get(&key).map_or(false, |audit_trail| {
-            audit_trail.get_entries().iter().any(|entry| {
-                entry.user_id == user_id
-                    && entry.action == AuditAction::ConsentGiven
-                    && entry.consent_given == Some(true)
-                    && entry
-                        .document_id
-                        .as_ref()
-                        .map_or(false, |doc_id| doc_id == document_id)
-            })
+            audit_trail
+                .get_entries()
+                .iter()
+                .rev()
+                .find(|entry| {
+                    entry.user_id == user_id
+                        && is_consent_decision(entry)
+                        && entry
+                            .document_id
+                            .as_ref()
+                            .map_or(false, |doc_id| doc_id == document_id)
+                })
+                .map_or(false, |entry| entry.consent_given == Some(true))
         })
     })
 }
@@ -347,6 +632,7 @@ fn create_context(request: CreateContextRequest) -> Result<(), Error> {
             context_status: ContextStatus::Active,
             metadata,
             created_at: current_time,
+            admin_signs: request.admin_signs,
         };
 
         contexts.insert(key, context_record);
@@ -361,6 +647,9 @@ fn create_context(request: CreateContextRequest) -> Result<(), Error> {
             consent_given: None,
             document_hash_after_action: None,
             metadata: Some("Context created".to_string()),
+            previous_hash: None,
+            entry_hash: None,
+            consent_text_hash: None,
         };
         add_audit_entry(&request.context_id, audit_entry);
         Ok(())
@@ -404,6 +693,9 @@ fn add_participant_to_context(context_id: String, participant_id: String) -> Res
                     consent_given: None,
                     document_hash_after_action: None,
                     metadata: Some(format!("Added participant: {}", participant_id)),
+                    previous_hash: None,
+                    entry_hash: None,
+                    consent_text_hash: None,
                 };
                 add_audit_entry(&context_id, audit_entry);
                 Ok(())
@@ -456,6 +748,7 @@ fn upload_document_to_context(request: DocumentUploadRequest) -> Result<(), Erro
             current_signers: Vec::new(),
             document_status: DocumentStatus::Pending,
             metadata,
+            deadline: request.deadline,
         };
 
         documents.insert(doc_key, document_record);
@@ -479,6 +772,9 @@ fn upload_document_to_context(request: DocumentUploadRequest) -> Result<(), Erro
             consent_given: None,
             document_hash_after_action: Some(request.document_hash),
             metadata: None,
+            previous_hash: None,
+            entry_hash: None,
+            consent_text_hash: None,
         };
         add_audit_entry(&request.context_id, audit_entry);
         Ok(())
@@ -486,9 +782,16 @@ fn upload_document_to_context(request: DocumentUploadRequest) -> Result<(), Erro
 }
 
 #[update]
-fn record_consent_for_context(context_id: String, document_id: String) -> Result<(), Error> {
+fn record_consent_for_context(
+    context_id: String,
+    document_id: String,
+    consent_text_hash: Option<String>,
+) -> Result<(), Error> {
     validate_id(&context_id)?;
     validate_id(&document_id)?;
+    if let Some(hash) = &consent_text_hash {
+        validate_hash(hash)?;
+    }
     let user_id = caller().to_string();
 
     // Check if user is a context participant
@@ -498,16 +801,12 @@ fn record_consent_for_context(context_id: String, document_id: String) -> Result
 
     // Verify the document exists and belongs to the context
     let doc_key = StorableString(document_id.clone());
-    let document_exists = DOCUMENTS.with(|documents| {
-        documents
-            .borrow()
-            .get(&doc_key)
-            .map_or(false, |document| document.context_id == context_id)
-    });
+    let document = DOCUMENTS
+        .with(|documents| documents.borrow().get(&doc_key))
+        .filter(|document| document.context_id == context_id)
+        .ok_or(Error::NotFound)?;
 
-    if !document_exists {
-        return Err(Error::NotFound);
-    }
+    ensure_not_expired(&document)?;
 
     let audit_entry = AuditEntry {
         entry_id: generate_audit_id(),
@@ -519,6 +818,54 @@ fn record_consent_for_context(context_id: String, document_id: String) -> Result
         consent_given: Some(true),
         document_hash_after_action: None,
         metadata: None,
+        previous_hash: None,
+        entry_hash: None,
+        consent_text_hash,
+    };
+    add_audit_entry(&context_id, audit_entry);
+    Ok(())
+}
+
+#[update]
+fn withdraw_consent(context_id: String, document_id: String) -> Result<(), Error> {
+    validate_id(&context_id)?;
+    validate_id(&document_id)?;
+    let user_id = caller().to_string();
+
+    if !is_context_participant(&context_id, &user_id) {
+        return Err(Error::Unauthorized);
+    }
+
+    let doc_key = StorableString(document_id.clone());
+    let document = DOCUMENTS
+        .with(|documents| documents.borrow().get(&doc_key))
+        .filter(|document| document.context_id == context_id)
+        .ok_or(Error::NotFound)?;
+
+    if !has_user_given_consent(&context_id, &user_id, &document_id) {
+        return Err(Error::UpdateConflict(
+            "User has not consented to this document.".to_string(),
+        ));
+    }
+    if document.current_signers.contains(&user_id) {
+        return Err(Error::UpdateConflict(
+            "User has already signed this document.".to_string(),
+        ));
+    }
+
+    let audit_entry = AuditEntry {
+        entry_id: generate_audit_id(),
+        user_id,
+        action: AuditAction::ConsentWithdrawn,
+        timestamp: time(),
+        context_id: context_id.clone(),
+        document_id: Some(document_id),
+        consent_given: Some(false),
+        document_hash_after_action: None,
+        metadata: None,
+        previous_hash: None,
+        entry_hash: None,
+        consent_text_hash: None,
     };
     add_audit_entry(&context_id, audit_entry);
     Ok(())
@@ -543,13 +890,19 @@ fn sign_document(request: SigningRequest) -> Result<(), Error> {
                     return Err(Error::Unauthorized);
                 }
 
-                // Check if already signed
+                // Checked first, so a repeated call is reported as such even once the
+                // document is fully signed or expired
                 if document.current_signers.contains(&user_id) {
                     return Err(Error::UpdateConflict(
                         "User has already signed this document.".to_string(),
                     ));
                 }
 
+                ensure_not_expired(&document)?;
+                if !document.document_status.is_open_for_signing() {
+                    return Err(Error::DocumentNotReady);
+                }
+
                 // Check consent requirement for this specific document
                 if !has_user_given_consent(&document.context_id, &user_id, &request.document_id) {
                     return Err(Error::ConsentRequired);
@@ -565,14 +918,12 @@ fn sign_document(request: SigningRequest) -> Result<(), Error> {
                         .borrow()
                         .get(&context_key)
                         .map_or(false, |context| {
-                            let mut required_signers: HashSet<String> =
-                                context.participants.iter().cloned().collect();
-                            required_signers.insert(context.admin_id.clone());
-
                             let current_signers: HashSet<String> =
                                 document.current_signers.iter().cloned().collect();
 
-                            required_signers.is_subset(&current_signers)
+                            required_signers(&context)
+                                .iter()
+                                .all(|signer| current_signers.contains(signer))
                         })
                 });
 
@@ -598,6 +949,9 @@ fn sign_document(request: SigningRequest) -> Result<(), Error> {
                     consent_given: Some(true),
                     document_hash_after_action: None,
                     metadata: Some(format!("Signed by: {}", user_id)),
+                    previous_hash: None,
+                    entry_hash: None,
+                    consent_text_hash: None,
                 };
                 add_audit_entry(&context_id, signature_entry);
 
@@ -613,6 +967,9 @@ fn sign_document(request: SigningRequest) -> Result<(), Error> {
                         consent_given: None,
                         document_hash_after_action: None,
                         metadata: Some("Document fully signed".to_string()),
+                        previous_hash: None,
+                        entry_hash: None,
+                        consent_text_hash: None,
                     };
                     add_audit_entry(&context_id, completion_entry);
                 }
@@ -624,6 +981,161 @@ fn sign_document(request: SigningRequest) -> Result<(), Error> {
     })
 }
 
+#[update]
+fn decline_document(document_id: String, reason: String) -> Result<(), Error> {
+    validate_id(&document_id)?;
+    if reason.trim().is_empty() {
+        return Err(Error::InvalidInput(
+            "A reason is required to decline a document.".to_string(),
+        ));
+    }
+
+    transition_document(
+        &document_id,
+        DocumentStatus::Declined,
+        AuditAction::DocumentDeclined,
+        Some(reason),
+        |document, user_id| {
+            if !is_context_participant(&document.context_id, user_id) {
+                return Err(Error::Unauthorized);
+            }
+            if document
+                .current_signers
+                .iter()
+                .any(|signer| signer == user_id)
+            {
+                return Err(Error::UpdateConflict(
+                    "User has already signed this document.".to_string(),
+                ));
+            }
+            Ok(())
+        },
+    )
+}
+
+#[update]
+fn void_document(document_id: String, reason: String) -> Result<(), Error> {
+    validate_id(&document_id)?;
+    if reason.trim().is_empty() {
+        return Err(Error::InvalidInput(
+            "A reason is required to void a document.".to_string(),
+        ));
+    }
+
+    transition_document(
+        &document_id,
+        DocumentStatus::Voided,
+        AuditAction::DocumentVoided,
+        Some(reason),
+        |document, user_id| match context_admin(&document.context_id) {
+            Some(admin_id) if admin_id == user_id => Ok(()),
+            _ => Err(Error::Unauthorized),
+        },
+    )
+}
+
+#[update]
+fn archive_document(document_id: String) -> Result<(), Error> {
+    validate_id(&document_id)?;
+
+    transition_document(
+        &document_id,
+        DocumentStatus::Archived,
+        AuditAction::DocumentArchived,
+        None,
+        |document, user_id| match context_admin(&document.context_id) {
+            Some(admin_id) if admin_id == user_id => Ok(()),
+            _ => Err(Error::Unauthorized),
+        },
+    )
+}
+
+/// Move every context and unsigned document whose deadline has passed to `Expired`
+#[update]
+fn sweep_expired() -> SweepReport {
+    let now = time();
+    let mut report = SweepReport {
+        expired_contexts: Vec::new(),
+        expired_documents: Vec::new(),
+    };
+
+    CONTEXTS.with(|contexts| {
+        let mut contexts = contexts.borrow_mut();
+        let expired: Vec<(StorableString, ContextRecord)> = contexts
+            .iter()
+            .filter(|(_, context)| {
+                context.context_status == ContextStatus::Active
+                    && context
+                        .metadata
+                        .expires_at
+                        .map_or(false, |expires_at| now >= expires_at)
+            })
+            .collect();
+
+        for (key, mut context) in expired {
+            context.context_status = ContextStatus::Expired;
+            let context_id = context.context_id.clone();
+            contexts.insert(key, context);
+            report.expired_contexts.push(context_id);
+        }
+    });
+
+    for context_id in &report.expired_contexts {
+        let audit_entry = AuditEntry {
+            entry_id: generate_audit_id(),
+            user_id: "system".to_string(),
+            action: AuditAction::ContextExpired,
+            timestamp: now,
+            context_id: context_id.clone(),
+            document_id: None,
+            consent_given: None,
+            document_hash_after_action: None,
+            metadata: Some("Context deadline passed".to_string()),
+            previous_hash: None,
+            entry_hash: None,
+            consent_text_hash: None,
+        };
+        add_audit_entry(context_id, audit_entry);
+    }
+
+    let expired: Vec<(StorableString, DocumentRecord)> = DOCUMENTS.with(|documents| {
+        documents
+            .borrow()
+            .iter()
+            .filter(|(_, document)| {
+                document.document_status.is_open_for_signing()
+                    && effective_deadline(document).map_or(false, |deadline| now >= deadline)
+            })
+            .collect()
+    });
+
+    for (key, mut document) in expired {
+        document.document_status = DocumentStatus::Expired;
+        let context_id = document.context_id.clone();
+        let document_id = document.document_id.clone();
+        DOCUMENTS.with(|documents| documents.borrow_mut().insert(key, document));
+
+        let audit_entry = AuditEntry {
+            entry_id: generate_audit_id(),
+            user_id: "system".to_string(),
+            action: AuditAction::DocumentExpired,
+            timestamp: now,
+            context_id: context_id.clone(),
+            document_id: Some(document_id.clone()),
+            consent_given: None,
+            document_hash_after_action: None,
+            metadata: Some("Signing deadline passed".to_string()),
+            previous_hash: None,
+            entry_hash: None,
+            consent_text_hash: None,
+        };
+        add_audit_entry(&context_id, audit_entry);
+        report.expired_documents.push(document_id);
+    }
+
+    report
+}
+
 #[update]
 fn record_final_hash(document_id: String, hash: String) -> Result<(), Error> {
     validate_id(&document_id)?;
@@ -671,6 +1183,9 @@ fn record_final_hash(document_id: String, hash: String) -> Result<(), Error> {
                     consent_given: None,
                     document_hash_after_action: Some(hash),
                     metadata: Some("Final hash recorded".to_string()),
+                    previous_hash: None,
+                    entry_hash: None,
+                    consent_text_hash: None,
                 };
                 add_audit_entry(&context_id, audit_entry);
                 Ok(())
@@ -765,6 +1280,68 @@ fn get_audit_trail_for_document(
     }))
 }
 
+#[query]
+fn verify_audit_chain(context_id: String) -> Result<ChainVerification, Error> {
+    validate_id(&context_id)?;
+    let key = StorableString(context_id);
+    if !CONTEXTS.with(|contexts| contexts.borrow().contains_key(&key)) {
+        return Err(Error::ContextNotFound);
+    }
+
+    Ok(AUDIT_TRAIL.with(|trail| {
+        trail
+            .borrow()
+            .get(&key)
+            .unwrap_or_else(AuditTrail::new)
+            .verify()
+    }))
+}
+
+/// Contexts and unsigned documents whose deadline falls within the next `within_seconds`,
+/// soonest first; overdue items not swept yet are included
+#[query]
+fn list_expiring_within(within_seconds: u64) -> Vec<ExpiringItem> {
+    let horizon = time().saturating_add(within_seconds.saturating_mul(1_000_000_000));
+    let mut items: Vec<ExpiringItem> = CONTEXTS.with(|contexts| {
+        contexts
+            .borrow()
+            .iter()
+            .filter(|(_, context)| context.context_status == ContextStatus::Active)
+            .filter_map(|(_, context)| {
+                context
+                    .metadata
+                    .expires_at
+                    .filter(|expires_at| *expires_at <= horizon)
+                    .map(|expires_at| ExpiringItem {
+                        context_id: context.context_id,
+                        document_id: None,
+                        expires_at,
+                    })
+            })
+            .collect()
+    });
+
+    DOCUMENTS.with(|documents| {
+        for (_, document) in documents.borrow().iter() {
+            if !document.document_status.is_open_for_signing() {
+                continue;
+            }
+            if let Some(expires_at) =
+                effective_deadline(&document).filter(|deadline| *deadline <= horizon)
+            {
+                items.push(ExpiringItem {
+                    context_id: document.context_id,
+                    document_id: Some(document.document_id),
+                    expires_at,
+                });
+            }
+        }
+    });
+
+    items.sort_by_key(|item| item.expires_at);
+    items
+}
+
 #[query]
 fn get_context_signing_progress(
     context_id: String,
@@ -779,26 +1356,30 @@ fn get_context_signing_progress(
             .ok_or(Error::ContextNotFound)
     })?;
 
-    let mut required_signers = context.participants.clone();
-    required_signers.push(context.admin_id);
-    required_signers.sort_unstable();
-    required_signers.dedup();
+    let required_signers = required_signers(&context);
 
-    // Get users who have given consent (now per document)
+    // Get users whose latest consent decision for some document is a consent
     let consented_users = AUDIT_TRAIL.with(|trail| {
         trail
             .borrow()
             .get(&context_key)
             .map_or_else(Vec::new, |audit_trail| {
-                audit_trail
-                    .get_entries()
-                    .iter()
-                    .filter(|entry| {
-                        entry.action == AuditAction::ConsentGiven
-                            && entry.consent_given == Some(true)
-                            && entry.document_id.is_some()
-                    })
-                    .map(|entry| entry.user_id.clone())
+                let mut latest: HashMap<(&str, &str), bool> = HashMap::new();
+                for entry in audit_trail.get_entries() {
+                    if let (true, Some(document_id)) =
+                        (is_consent_decision(entry), entry.document_id.as_deref())
+                    {
+                        latest.insert(
+                            (entry.user_id.as_str(), document_id),
+                            entry.consent_given == Some(true),
+                        );
+                    }
+                }
+
+                latest
+                    .into_iter()
+                    .filter(|(_, consented)| *consented)
+                    .map(|((user_id, _), _)| user_id.to_string())
                     .collect::<HashSet<String>>()
                     .into_iter()
                     .collect()
@@ -0,0 +1,198 @@
+use core::fmt;
+
+use calimero_sdk::serde::Serialize;
+
+use crate::types::hash::Sha256Digest;
+use crate::types::id::{DocumentId, UserId};
+use crate::{Capability, DocumentStatus};
+
+/// Error returned by every public `MeroDocsState` method.
+///
+/// Serialized as `{ "code": "NOT_FOUND", "details": { ... } }`; the codes are derived from the
+/// variant names and are part of the public API, so variants must not be renamed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+#[serde(tag = "code", content = "details", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MeroDocsError {
+    InvalidInput(String),
+    NotFound {
+        resource: Resource,
+        id: String,
+    },
+    AlreadyExists {
+        resource: Resource,
+        id: String,
+    },
+    WrongContextType {
+        expected: ContextKind,
+    },
+    NotParticipant {
+        user_id: UserId,
+    },
+    PermissionDenied {
+        required: Capability,
+    },
+    Unauthorized(String),
+    ConsentRequired {
+        user_id: UserId,
+        document_id: DocumentId,
+    },
+    UpdateConflict(String),
+    NotSignersTurn {
+        next: Vec<UserId>,
+    },
+    InvalidSignature {
+        signer: UserId,
+    },
+    HashMismatch {
+        supplied: Sha256Digest,
+        computed: Sha256Digest,
+    },
+    SizeMismatch {
+        supplied: u64,
+        computed: u64,
+    },
+    BlobUnavailable {
+        blob_id: String,
+    },
+    DocumentNotReady(String),
+    InvalidStatusTransition {
+        from: DocumentStatus,
+        to: DocumentStatus,
+    },
+    DeadlinePassed {
+        deadline: u64,
+    },
+    ContextExpired,
+    StorageError(String),
+}
+
+/// Kind of entity a `NotFound` or `AlreadyExists` error refers to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum Resource {
+    Document,
+    DocumentRevision,
+    Signature,
+    Participant,
+    Invitation,
+    JoinedContext,
+    IdentityMapping,
+}
+
+/// Context type a method is restricted to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum ContextKind {
+    Private,
+    Shared,
+}
+
+impl MeroDocsError {
+    /// Stable, machine-readable error code, identical to the serialized `code`
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidInput(_) => "INVALID_INPUT",
+            Self::NotFound { .. } => "NOT_FOUND",
+            Self::AlreadyExists { .. } => "ALREADY_EXISTS",
+            Self::WrongContextType { .. } => "WRONG_CONTEXT_TYPE",
+            Self::NotParticipant { .. } => "NOT_PARTICIPANT",
+            Self::PermissionDenied { .. } => "PERMISSION_DENIED",
+            Self::Unauthorized(_) => "UNAUTHORIZED",
+            Self::ConsentRequired { .. } => "CONSENT_REQUIRED",
+            Self::UpdateConflict(_) => "UPDATE_CONFLICT",
+            Self::NotSignersTurn { .. } => "NOT_SIGNERS_TURN",
+            Self::InvalidSignature { .. } => "INVALID_SIGNATURE",
+            Self::HashMismatch { .. } => "HASH_MISMATCH",
+            Self::SizeMismatch { .. } => "SIZE_MISMATCH",
+            Self::BlobUnavailable { .. } => "BLOB_UNAVAILABLE",
+            Self::DocumentNotReady(_) => "DOCUMENT_NOT_READY",
+            Self::InvalidStatusTransition { .. } => "INVALID_STATUS_TRANSITION",
+            Self::DeadlinePassed { .. } => "DEADLINE_PASSED",
+            Self::ContextExpired => "CONTEXT_EXPIRED",
+            Self::StorageError(_) => "STORAGE_ERROR",
+        }
+    }
+
+    pub(crate) fn not_found(resource: Resource, id: impl fmt::Display) -> Self {
+        Self::NotFound {
+            resource,
+            id: id.to_string(),
+        }
+    }
+
+    pub(crate) fn already_exists(resource: Resource, id: impl fmt::Display) -> Self {
+        Self::AlreadyExists {
+            resource,
+            id: id.to_string(),
+        }
+    }
+
+    pub(crate) fn storage(action: &str, error: impl fmt::Debug) -> Self {
+        Self::StorageError(format!("{}: {:?}", action, error))
+    }
+}
+
+impl fmt::Display for MeroDocsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInput(reason) => write!(f, "Invalid input: {}", reason),
+            Self::NotFound { resource, id } => write!(f, "{:?} not found: {}", resource, id),
+            Self::AlreadyExists { resource, id } => {
+                write!(f, "{:?} already exists: {}", resource, id)
+            }
+            Self::WrongContextType { expected } => {
+                write!(
+                    f,
+                    "This method can only be called from a {:?} context",
+                    expected
+                )
+            }
+            Self::NotParticipant { user_id } => {
+                write!(f, "User {} is not a participant of this context", user_id)
+            }
+            Self::PermissionDenied { required } => {
+                write!(f, "{:?} capability required for this operation", required)
+            }
+            Self::Unauthorized(reason) => write!(f, "Unauthorized: {}", reason),
+            Self::ConsentRequired {
+                user_id,
+                document_id,
+            } => write!(
+                f,
+                "User {} must provide consent for document {} first",
+                user_id, document_id
+            ),
+            Self::UpdateConflict(reason) => write!(f, "Update conflict: {}", reason),
+            Self::NotSignersTurn { next } => {
+                let next: Vec<String> = next.iter().map(ToString::to_string).collect();
+                write!(f, "It is not this signer's turn; next: {}", next.join(", "))
+            }
+            Self::InvalidSignature { signer } => write!(
+                f,
+                "Signature does not match signer {} and the document hash",
+                signer
+            ),
+            Self::HashMismatch { supplied, computed } => write!(
+                f,
+                "Hash mismatch: supplied {}, blob has {}",
+                supplied, computed
+            ),
+            Self::SizeMismatch { supplied, computed } => write!(
+                f,
+                "Size mismatch: supplied {} bytes, blob has {} bytes",
+                supplied, computed
+            ),
+            Self::BlobUnavailable { blob_id } => write!(f, "Failed to open blob {}", blob_id),
+            Self::DocumentNotReady(reason) => write!(f, "Document not ready: {}", reason),
+            Self::InvalidStatusTransition { from, to } => {
+                write!(f, "Cannot move a document from {:?} to {:?}", from, to)
+            }
+            Self::DeadlinePassed { deadline } => {
+                write!(f, "The signing deadline passed at {}", deadline)
+            }
+            Self::ContextExpired => write!(f, "The context has expired"),
+            Self::StorageError(reason) => write!(f, "Storage error: {}", reason),
+        }
+    }
+}
@@ -4,9 +4,24 @@ use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use calimero_sdk::serde::{Deserialize, Serialize};
 use calimero_sdk::{app, env};
 use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
 
+mod error;
+mod migrations;
 mod types;
-use types::id::UserId;
+
+#[cfg(test)]
+mod tests;
+pub use error::{ContextKind, MeroDocsError, Resource};
+use types::hash::Sha256Digest;
+use types::id::{ConsentKey, DocumentId, Ed25519Signature, UserId};
+
+/// Chunk size used when streaming blobs through the hasher
+const BLOB_READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Maximum number of audit entries returned by a single `get_audit_log` call
+const MAX_AUDIT_PAGE_SIZE: u64 = 100;
 
 fn encode_blob_id_base58(blob_id_bytes: &[u8; 32]) -> String {
     let mut buf = [0u8; 44];
@@ -14,20 +29,23 @@ fn encode_blob_id_base58(blob_id_bytes: &[u8; 32]) -> String {
     std::str::from_utf8(&buf[..len]).unwrap().to_owned()
 }
 
-fn parse_blob_id_base58(blob_id_str: &str) -> Result<[u8; 32], String> {
+fn parse_blob_id_base58(blob_id_str: &str) -> Result<[u8; 32], MeroDocsError> {
     match bs58::decode(blob_id_str).into_vec() {
         Ok(bytes) => {
             if bytes.len() != 32 {
-                return Err(format!(
+                return Err(MeroDocsError::InvalidInput(format!(
                     "Invalid blob ID length: expected 32 bytes, got {}",
                     bytes.len()
-                ));
+                )));
             }
             let mut blob_id = [0u8; 32];
             blob_id.copy_from_slice(&bytes);
             Ok(blob_id)
         }
-        Err(e) => Err(format!("Failed to decode blob ID '{}': {}", blob_id_str, e)),
+        Err(e) => Err(MeroDocsError::InvalidInput(format!(
+            "Failed to decode blob ID '{}': {}",
+            blob_id_str, e
+        ))),
     }
 }
 
@@ -49,6 +67,22 @@ pub struct SignatureRecord {
     pub blob_id: [u8; 32],
     pub size: u64,
     pub created_at: u64,
+    pub kind: SignatureKind,
+    pub mime_type: String,
+    pub is_default: bool, // At most one signature of each kind is the default
+}
+
+/// What a stored signature image is used for
+#[derive(
+    Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum SignatureKind {
+    Signature,
+    Initials,
+    Stamp,
+    Seal,
 }
 
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
@@ -60,17 +94,6 @@ pub struct ContextAgreement {
     pub joined_at: u64,
 }
 
-/// Participant roles in shared contexts
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
-#[borsh(crate = "calimero_sdk::borsh")]
-#[serde(crate = "calimero_sdk::serde")]
-pub enum ParticipantRole {
-    Owner,
-    Signer,
-    Viewer,
-    Unknown,
-}
-
 /// Document chunk with its embedding
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
@@ -87,9 +110,9 @@ pub struct DocumentChunk {
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
 pub struct DocumentInfo {
-    pub id: String,
+    pub id: DocumentId,
     pub name: String,
-    pub hash: String,
+    pub hash: Sha256Digest,
     pub uploaded_by: UserId,
     pub uploaded_at: u64,
     pub status: DocumentStatus,
@@ -99,16 +122,183 @@ pub struct DocumentInfo {
     pub embeddings: Option<Vec<f32>>,
     pub extracted_text: Option<String>,
     pub chunks: Option<Vec<DocumentChunk>>,
+    pub required_signers: Vec<UserId>,
+    pub signing_mode: SigningMode,
+}
+
+/// Order in which the required signers of a document may sign
+#[derive(
+    Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum SigningMode {
+    /// Signers may sign in any order
+    Parallel,
+    /// Signers must sign in the order they were listed at upload
+    Sequential,
+}
+
+/// Signing progress of a document and whose turn it is
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SigningQueue {
+    pub document_id: DocumentId,
+    pub signing_mode: SigningMode,
+    pub signed: Vec<UserId>,
+    pub pending: Vec<UserId>,
+    pub next: Vec<UserId>,
+}
+
+impl SigningQueue {
+    /// Status implied by the signatures collected so far
+    pub fn status(&self) -> DocumentStatus {
+        if self.pending.is_empty() {
+            DocumentStatus::FullySigned
+        } else if self.signed.is_empty() {
+            DocumentStatus::Pending
+        } else {
+            DocumentStatus::PartiallySigned
+        }
+    }
 }
 
 /// Document status tracking
-#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize)]
+///
+/// New variants are appended so the Borsh discriminants of stored documents stay valid.
+#[derive(Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
 pub enum DocumentStatus {
     Pending,
     PartiallySigned,
     FullySigned,
+    /// Uploaded but not yet sent out for signing
+    Draft,
+    /// Rejected by one of its required signers
+    Declined,
+    /// Withdrawn by an admin
+    Voided,
+    Expired,
+    Archived,
+}
+
+impl DocumentStatus {
+    /// Whether signers can still consent to and sign the document
+    pub fn is_open_for_signing(&self) -> bool {
+        matches!(self, Self::Pending | Self::PartiallySigned)
+    }
+
+    /// Document lifecycle state machine
+    pub fn can_transition_to(&self, next: &DocumentStatus) -> bool {
+        use DocumentStatus::*;
+
+        matches!(
+            (self, next),
+            (Draft, Pending)
+                | (Pending, PartiallySigned | FullySigned)
+                | (PartiallySigned, PartiallySigned | FullySigned)
+                | (Pending | PartiallySigned, Declined | Expired)
+                | (Draft | Pending | PartiallySigned, Voided)
+                | (FullySigned | Declined | Voided | Expired, Archived)
+        )
+    }
+}
+
+/// A participant's consent decision for a document; the latest record is authoritative
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ConsentRecord {
+    /// `false` when the participant withdrew an earlier consent
+    pub given: bool,
+    /// Version label of the consent/disclosure text the participant accepted
+    pub disclosure_version: Option<String>,
+    /// SHA-256 of the consent/disclosure text the participant accepted
+    pub disclosure_hash: Option<Sha256Digest>,
+    /// `0` for consents recorded before consent history was kept
+    pub recorded_at: u64,
+}
+
+/// Lifecycle state of a shared context
+#[derive(
+    Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum ContextStatus {
+    Active,
+    /// The context deadline passed; no further consents or signatures are accepted
+    Expired,
+}
+
+/// Invitation to join a shared context, waiting for the invitee to accept or decline
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct Invitation {
+    pub user_id: UserId,
+    pub role: PermissionLevel,
+    pub invited_by: UserId,
+    pub invited_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+impl Invitation {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+/// Document or context whose signing deadline is coming up
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ExpiringItem {
+    /// `None` for the context deadline itself
+    pub document_id: Option<DocumentId>,
+    pub deadline: u64,
+}
+
+/// What a call to `sweep_expired` moved to `Expired`
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SweepReport {
+    pub context_expired: bool,
+    pub expired_documents: Vec<DocumentId>,
+}
+
+/// Why a new revision of a document was recorded
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum RevisionReason {
+    Upload,
+    Signature,
+    Amendment,
+    /// Imported from a v0 state, where the document was keyed by `legacy_id`
+    /// (`doc_<timestamp>_<name>`) and carried the caller-supplied `legacy_hash`
+    Migrated {
+        legacy_id: String,
+        legacy_hash: String,
+    },
+}
+
+/// A single version of a document's PDF, kept so earlier versions stay retrievable
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentRevision {
+    pub revision: u64,
+    #[serde(serialize_with = "serialize_blob_id_bytes")]
+    pub blob_id: [u8; 32],
+    pub hash: Sha256Digest,
+    pub size: u64,
+    pub author: UserId,
+    pub created_at: u64,
+    pub reason: RevisionReason,
 }
 
 /// Signature record for documents
@@ -116,11 +306,107 @@ pub enum DocumentStatus {
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
 pub struct DocumentSignature {
+    pub signer: UserId,
+    /// Identity that executed the signing; differs from `signer` for delegated signatures
+    pub signed_by: UserId,
+    pub signed_at: u64,
+    /// Document hash the signer approved
+    pub document_hash: Sha256Digest,
+    /// Signer's ed25519 signature over `(context_id, document_id, document_hash)`; absent when the
+    /// signing client could not produce one, and for signatures recorded before verification
+    pub signature: Option<Ed25519Signature>,
+}
+
+/// Result of re-checking a stored document signature
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SignatureVerification {
     pub signer: UserId,
     pub signed_at: u64,
+    pub document_hash: Sha256Digest,
+    pub valid: bool,
+}
+
+/// Action recorded in the shared context's audit log
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum AuditAction {
+    DocumentUploaded,
+    DocumentAmended,
+    DocumentDeleted,
+    ConsentGiven,
+    SignatureApplied,
+    DocumentCompleted,
+    /// No longer recorded since participants join through `accept_invitation`; kept so earlier
+    /// entries still deserialize
+    ParticipantAdded,
+    ParticipantRemoved,
+    PermissionChanged,
+    DocumentPublished,
+    DocumentDeclined,
+    DocumentVoided,
+    DocumentArchived,
+    DeadlineSet,
+    DocumentExpired,
+    ContextExpired,
+    ConsentWithdrawn,
+    ParticipantInvited,
+    InvitationAccepted,
+    InvitationDeclined,
+}
+
+/// Append-only record of a state change in the shared context, chained to its predecessor
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct AuditEntry {
+    pub sequence: u64,
+    pub actor: UserId,
+    pub action: AuditAction,
+    pub timestamp: u64,
+    pub document_id: Option<DocumentId>,
+    pub subject: Option<UserId>, // Participant the action applies to, if any
+    pub document_hash: Option<Sha256Digest>,
+    pub details: Option<String>,
+    pub previous_hash: Option<Sha256Digest>, // `entry_hash` of the preceding entry
+    pub entry_hash: Sha256Digest,
+}
+
+impl AuditEntry {
+    /// SHA-256 over the Borsh encoding of every field except `entry_hash`
+    pub fn compute_hash(&self) -> Sha256Digest {
+        let encoded = calimero_sdk::borsh::to_vec(&(
+            &self.previous_hash,
+            self.sequence,
+            &self.actor,
+            &self.action,
+            self.timestamp,
+            &self.document_id,
+            &self.subject,
+            &self.document_hash,
+            &self.details,
+        ))
+        .expect("serializing an audit entry cannot fail");
+
+        Sha256Digest::new(Sha256::digest(encoded).into())
+    }
+}
+
+/// Outcome of recomputing the audit log's hash chain
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct AuditChainVerification {
+    pub entries_checked: u64,
+    /// Hash of the last entry, committing to the full history; `None` for an empty log
+    pub head_hash: Option<Sha256Digest>,
+    /// Sequence number of the first entry whose hash or link does not match
+    pub first_broken_link: Option<u64>,
 }
 
-/// Permission levels for participants
+/// Participant roles; each role grants a fixed bundle of capabilities
 #[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
@@ -130,10 +416,68 @@ pub enum PermissionLevel {
     Admin,
 }
 
+impl PermissionLevel {
+    pub fn capabilities(&self) -> &'static [Capability] {
+        match self {
+            Self::Read => &[
+                Capability::Download,
+                Capability::Comment,
+                Capability::ViewAudit,
+            ],
+            Self::Sign => &[
+                Capability::Download,
+                Capability::Comment,
+                Capability::ViewAudit,
+                Capability::Upload,
+                Capability::Sign,
+            ],
+            Self::Admin => Capability::ALL,
+        }
+    }
+
+    pub fn has(&self, capability: Capability) -> bool {
+        self.capabilities().contains(&capability)
+    }
+}
+
+/// A single action a participant may be allowed to perform in a shared context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum Capability {
+    Upload,
+    Delete,
+    Invite,
+    Sign,
+    Comment,
+    Download,
+    ManagePermissions,
+    ViewAudit,
+    ManageDocuments, // Amend, publish, void, archive and set deadlines on others' documents
+    DelegateSigning, // Sign on behalf of another participant
+}
+
+impl Capability {
+    pub const ALL: &'static [Capability] = &[
+        Capability::Upload,
+        Capability::Delete,
+        Capability::Invite,
+        Capability::Sign,
+        Capability::Comment,
+        Capability::Download,
+        Capability::ManagePermissions,
+        Capability::ViewAudit,
+        Capability::ManageDocuments,
+        Capability::DelegateSigning,
+    ];
+}
+
 #[app::state(emits = MeroDocsEvent)]
 #[derive(BorshDeserialize, BorshSerialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 pub struct MeroDocsState {
+    // Layout version, always serialized first; see `migrations`
+    pub schema_version: u32,
+
     // Context type flag
     pub is_private: bool,
 
@@ -148,10 +492,23 @@ pub struct MeroDocsState {
 
     // Shared context data
     pub participants: UnorderedSet<UserId>,
-    pub documents: UnorderedMap<String, DocumentInfo>,
-    pub document_signatures: UnorderedMap<String, Vector<DocumentSignature>>,
-    pub permissions: UnorderedMap<String, PermissionLevel>,
-    pub consents: UnorderedMap<String, bool>, // "user_id|document_id" -> consent
+    pub documents: UnorderedMap<DocumentId, DocumentInfo>,
+    pub document_signatures: UnorderedMap<DocumentId, Vector<DocumentSignature>>,
+    pub document_revisions: UnorderedMap<DocumentId, Vector<DocumentRevision>>,
+    pub document_count: u64, // Monotonic counter mixed into document IDs
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub consents: UnorderedMap<ConsentKey, Vector<ConsentRecord>>,
+    pub audit_log: Vector<AuditEntry>,
+    pub context_status: ContextStatus,
+    // Signing deadlines as `env::time_now()` timestamps; a document's effective deadline is the
+    // earlier of its own and the context's
+    pub context_deadline: Option<u64>,
+    pub document_deadlines: UnorderedMap<DocumentId, u64>,
+    // Invitees only become participants once they accept
+    pub pending_invitations: UnorderedMap<UserId, Invitation>,
+    // Mappings of contexts that were left, oldest first; `identity_mappings` holds active ones
+    pub archived_identity_mappings: UnorderedMap<String, Vector<IdentityMapping>>,
+    pub shared_identity_index: UnorderedMap<UserId, String>, // shared_identity -> context_id of active mappings
 }
 
 /// Metadata for tracking joined shared contexts
@@ -161,10 +518,46 @@ pub struct MeroDocsState {
 pub struct ContextMetadata {
     pub context_id: String,
     pub context_name: String,
-    pub role: ParticipantRole,
+    pub role: Option<PermissionLevel>, // Role in the shared context, if known
     pub joined_at: u64,
     pub private_identity: UserId, // User's private context identity
     pub shared_identity: UserId,  // User's identity in this shared context
+    pub membership: MembershipStatus,
+    pub document_count: Option<u64>, // As of the last sync
+    pub synced_at: Option<u64>,
+}
+
+/// Whether the user still belongs to a joined shared context
+#[derive(
+    Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum MembershipStatus {
+    Active,
+    /// An admin removed the user from the shared context; it may be joined again
+    Removed,
+}
+
+/// A user's membership of a shared context, as reported by that context for `sync_joined_contexts`
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct MembershipSnapshot {
+    pub context_id: String,
+    pub user_id: UserId,
+    /// `None` if the user is not a participant
+    pub role: Option<PermissionLevel>,
+    pub document_count: u64,
+}
+
+/// Joined contexts refreshed or marked as removed by `sync_joined_contexts`
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SyncReport {
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
 }
 
 /// Identity mapping for tracking user identities across contexts
@@ -176,6 +569,7 @@ pub struct IdentityMapping {
     pub shared_identity: UserId,  // Identity used in specific shared context
     pub context_id: String,       // Which shared context this mapping is for
     pub created_at: u64,          // When this mapping was created
+    pub left_at: Option<u64>,     // When the context was left; `None` while the mapping is active
 }
 
 /// Participant information with permission level
@@ -215,6 +609,12 @@ pub enum MeroDocsEvent {
     SignatureDeleted {
         id: u64,
     },
+    SignatureUpdated {
+        id: u64,
+        name: String,
+        kind: SignatureKind,
+        is_default: bool,
+    },
     ContextJoined {
         context_id: String,
         context_name: String,
@@ -225,20 +625,39 @@ pub enum MeroDocsEvent {
 
     // Shared context events
     DocumentUploaded {
-        id: String,
+        id: DocumentId,
         name: String,
+        hash: Sha256Digest,
         uploaded_by: UserId,
     },
     DocumentDeleted {
-        id: String,
+        id: DocumentId,
+    },
+    DocumentAmended {
+        document_id: DocumentId,
+        revision: u64,
+    },
+    ConsentGiven {
+        document_id: DocumentId,
+        user_id: UserId,
+        disclosure_hash: Option<Sha256Digest>,
+    },
+    ConsentWithdrawn {
+        document_id: DocumentId,
+        user_id: UserId,
     },
     DocumentSigned {
-        document_id: String,
+        document_id: DocumentId,
         signer: UserId,
+        signed_by: UserId,
+    },
+    DocumentCompleted {
+        document_id: DocumentId,
+        final_hash: Sha256Digest,
     },
     ParticipantInvited {
         user_id: UserId,
-        role: ParticipantRole,
+        role: PermissionLevel,
     },
     ParticipantJoined {
         user_id: UserId,
@@ -246,6 +665,22 @@ pub enum MeroDocsEvent {
     ParticipantLeft {
         user_id: UserId,
     },
+    DocumentStatusChanged {
+        document_id: DocumentId,
+        from: DocumentStatus,
+        to: DocumentStatus,
+        changed_by: UserId,
+        reason: Option<String>,
+    },
+    PermissionChanged {
+        user_id: UserId,
+        from: PermissionLevel,
+        to: PermissionLevel,
+        changed_by: UserId,
+    },
+    InvitationDeclined {
+        user_id: UserId,
+    },
 }
 
 #[app::logic]
@@ -256,6 +691,7 @@ impl MeroDocsState {
         let owner = UserId::new(owner_raw);
 
         let mut state = MeroDocsState {
+            schema_version: migrations::SCHEMA_VERSION,
             is_private,
             owner,
             context_name,
@@ -267,15 +703,23 @@ impl MeroDocsState {
             participants: UnorderedSet::new(),
             documents: UnorderedMap::new(),
             document_signatures: UnorderedMap::new(),
+            document_revisions: UnorderedMap::new(),
+            document_count: 0,
             permissions: UnorderedMap::new(),
             consents: UnorderedMap::new(),
+            audit_log: Vector::new(),
+            context_status: ContextStatus::Active,
+            context_deadline: None,
+            document_deadlines: UnorderedMap::new(),
+            pending_invitations: UnorderedMap::new(),
+            archived_identity_mappings: UnorderedMap::new(),
+            shared_identity_index: UnorderedMap::new(),
         };
 
         // For shared contexts, add the creator as a participant with admin permissions
         if !is_private {
             let _ = state.participants.insert(owner);
-            let owner_str = format!("{:?}", owner);
-            let _ = state.permissions.insert(owner_str, PermissionLevel::Admin);
+            let _ = state.permissions.insert(owner, PermissionLevel::Admin);
         }
 
         state
@@ -291,10 +735,25 @@ impl MeroDocsState {
         name: String,
         blob_id_str: String,
         data_size: u64,
-    ) -> Result<u64, String> {
+        kind: Option<SignatureKind>,
+        mime_type: Option<String>,
+    ) -> Result<u64, MeroDocsError> {
         if !self.is_private {
-            return Err("Signatures can only be created in private context".to_string());
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
+        }
+
+        let kind = kind.unwrap_or(SignatureKind::Signature);
+        let mime_type = mime_type.unwrap_or_else(|| "image/png".to_string());
+        if !mime_type.starts_with("image/") {
+            return Err(MeroDocsError::InvalidInput(format!(
+                "Unsupported signature MIME type: {}",
+                mime_type
+            )));
         }
+        // The first signature of a kind becomes its default
+        let is_default = self.default_signature_of(kind)?.is_none();
 
         let signature_id = self.signature_count;
         self.signature_count += 1;
@@ -321,11 +780,14 @@ impl MeroDocsState {
             blob_id,
             size: data_size,
             created_at: env::time_now(),
+            kind,
+            mime_type,
+            is_default,
         };
 
         self.signatures
             .insert(signature_id.to_string(), signature)
-            .map_err(|e| format!("Failed to store signature: {:?}", e))?;
+            .map_err(|e| MeroDocsError::storage("Failed to store signature", e))?;
 
         app::emit!(MeroDocsEvent::SignatureCreated {
             id: signature_id,
@@ -336,28 +798,111 @@ impl MeroDocsState {
         Ok(signature_id)
     }
 
-    /// Delete a signature by ID
-    pub fn delete_signature(&mut self, signature_id: u64) -> Result<(), String> {
+    /// Delete a signature by ID; if it was the default of its kind, the oldest remaining signature
+    /// of that kind becomes the default
+    pub fn delete_signature(&mut self, signature_id: u64) -> Result<(), MeroDocsError> {
         if !self.is_private {
-            return Err("Signatures can only be deleted in private context".to_string());
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
         }
 
         let key = signature_id.to_string();
 
-        match self.signatures.remove(&key) {
-            Ok(Some(_)) => {
-                app::emit!(MeroDocsEvent::SignatureDeleted { id: signature_id });
-                Ok(())
+        let removed = match self.signatures.remove(&key) {
+            Ok(Some(signature)) => signature,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Signature, signature_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to delete signature", e)),
+        };
+
+        app::emit!(MeroDocsEvent::SignatureDeleted { id: signature_id });
+
+        // The oldest remaining signature of the kind takes over as its default
+        if removed.is_default {
+            let successor = self
+                .signatures
+                .entries()
+                .map_err(|e| MeroDocsError::storage("Failed to list signatures", e))?
+                .map(|(_, signature)| signature)
+                .filter(|signature| signature.kind == removed.kind)
+                .min_by_key(|signature| signature.id);
+            if let Some(mut successor) = successor {
+                successor.is_default = true;
+                self.update_signature(successor)?;
             }
-            Ok(None) => Err(format!("Signature not found: {}", signature_id)),
-            Err(e) => Err(format!("Failed to delete signature: {:?}", e)),
+        }
+
+        Ok(())
+    }
+
+    /// Get a signature by ID
+    pub fn get_signature(&self, signature_id: u64) -> Result<SignatureRecord, MeroDocsError> {
+        if !self.is_private {
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
+        }
+
+        match self.signatures.get(&signature_id.to_string()) {
+            Ok(Some(signature)) => Ok(signature),
+            Ok(None) => Err(MeroDocsError::not_found(Resource::Signature, signature_id)),
+            Err(e) => Err(MeroDocsError::storage("Failed to get signature", e)),
         }
     }
 
+    /// Rename a signature
+    pub fn rename_signature(
+        &mut self,
+        signature_id: u64,
+        name: String,
+    ) -> Result<(), MeroDocsError> {
+        if name.trim().is_empty() {
+            return Err(MeroDocsError::InvalidInput(
+                "Signature name cannot be empty".to_string(),
+            ));
+        }
+
+        let mut signature = self.get_signature(signature_id)?;
+        signature.name = name;
+        self.update_signature(signature)
+    }
+
+    /// Make a signature the default of its kind, replacing the previous default
+    pub fn set_default_signature(&mut self, signature_id: u64) -> Result<(), MeroDocsError> {
+        let mut signature = self.get_signature(signature_id)?;
+        if signature.is_default {
+            return Ok(());
+        }
+
+        if let Some(mut previous) = self.default_signature_of(signature.kind)? {
+            previous.is_default = false;
+            self.update_signature(previous)?;
+        }
+
+        signature.is_default = true;
+        self.update_signature(signature)
+    }
+
+    /// Default signature of a kind, for the signing UI to apply without asking
+    pub fn get_default_signature(
+        &self,
+        kind: SignatureKind,
+    ) -> Result<Option<SignatureRecord>, MeroDocsError> {
+        if !self.is_private {
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
+        }
+
+        self.default_signature_of(kind)
+    }
+
     /// Get all signatures
-    pub fn list_signatures(&self) -> Result<Vec<SignatureRecord>, String> {
+    pub fn list_signatures(&self) -> Result<Vec<SignatureRecord>, MeroDocsError> {
         if !self.is_private {
-            return Err("Signatures can only be accessed in private context".to_string());
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
         }
 
         let mut signatures = Vec::new();
@@ -369,19 +914,64 @@ impl MeroDocsState {
         Ok(signatures)
     }
 
+    fn default_signature_of(
+        &self,
+        kind: SignatureKind,
+    ) -> Result<Option<SignatureRecord>, MeroDocsError> {
+        let entries = self
+            .signatures
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to list signatures", e))?;
+        for (_, signature) in entries {
+            if signature.kind == kind && signature.is_default {
+                return Ok(Some(signature));
+            }
+        }
+        Ok(None)
+    }
+
+    fn update_signature(&mut self, signature: SignatureRecord) -> Result<(), MeroDocsError> {
+        let id = signature.id;
+        let name = signature.name.clone();
+        let kind = signature.kind;
+        let is_default = signature.is_default;
+
+        self.signatures
+            .insert(id.to_string(), signature)
+            .map_err(|e| MeroDocsError::storage("Failed to update signature", e))?;
+
+        app::emit!(MeroDocsEvent::SignatureUpdated {
+            id,
+            name,
+            kind,
+            is_default,
+        });
+
+        Ok(())
+    }
+
     /// Join a shared context with identity mapping
     pub fn join_shared_context(
         &mut self,
         context_id: String,
         shared_identity: UserId,
         context_name: String,
-    ) -> Result<(), String> {
+    ) -> Result<(), MeroDocsError> {
         if !self.is_private {
-            return Err("Context joining can only be managed in private context".to_string());
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
         }
 
-        if self.joined_contexts.contains(&context_id).unwrap_or(false) {
-            return Err("Already joined this context".to_string());
+        let existing = self
+            .joined_contexts
+            .get(&context_id)
+            .map_err(|e| MeroDocsError::storage("Failed to get joined context", e))?;
+        if existing.is_some_and(|metadata| metadata.membership == MembershipStatus::Active) {
+            return Err(MeroDocsError::already_exists(
+                Resource::JoinedContext,
+                context_id,
+            ));
         }
 
         let private_identity = self.owner;
@@ -389,26 +979,22 @@ impl MeroDocsState {
         let metadata = ContextMetadata {
             context_id: context_id.clone(),
             context_name: context_name.clone(),
-            role: ParticipantRole::Unknown,
+            role: None,
             joined_at: env::time_now(),
             private_identity,
             shared_identity,
+            membership: MembershipStatus::Active,
+            document_count: None,
+            synced_at: None,
         };
 
-        let identity_mapping = IdentityMapping {
-            private_identity,
-            shared_identity,
-            context_id: context_id.clone(),
-            created_at: env::time_now(),
-        };
+        // A mapping left behind by an older release must not be silently overwritten
+        self.archive_identity_mapping(&context_id)?;
+        self.activate_identity_mapping(&metadata)?;
 
         self.joined_contexts
             .insert(context_id.clone(), metadata)
-            .map_err(|e| format!("Failed to join context: {:?}", e))?;
-
-        self.identity_mappings
-            .insert(context_id.clone(), identity_mapping)
-            .map_err(|e| format!("Failed to store identity mapping: {:?}", e))?;
+            .map_err(|e| MeroDocsError::storage("Failed to join context", e))?;
 
         app::emit!(MeroDocsEvent::ContextJoined {
             context_id,
@@ -418,25 +1004,33 @@ impl MeroDocsState {
     }
 
     /// Leave a shared context
-    pub fn leave_shared_context(&mut self, context_id: String) -> Result<(), String> {
+    pub fn leave_shared_context(&mut self, context_id: String) -> Result<(), MeroDocsError> {
         if !self.is_private {
-            return Err("Context leaving can only be managed in private context".to_string());
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
         }
 
         match self.joined_contexts.remove(&context_id) {
             Ok(Some(_)) => {
+                self.archive_identity_mapping(&context_id)?;
                 app::emit!(MeroDocsEvent::ContextLeft { context_id });
                 Ok(())
             }
-            Ok(None) => Err("Context not found".to_string()),
-            Err(e) => Err(format!("Failed to leave context: {:?}", e)),
+            Ok(None) => Err(MeroDocsError::not_found(
+                Resource::JoinedContext,
+                context_id,
+            )),
+            Err(e) => Err(MeroDocsError::storage("Failed to leave context", e)),
         }
     }
 
     /// List all joined contexts
-    pub fn list_joined_contexts(&self) -> Result<Vec<ContextMetadata>, String> {
+    pub fn list_joined_contexts(&self) -> Result<Vec<ContextMetadata>, MeroDocsError> {
         if !self.is_private {
-            return Err("Joined contexts can only be accessed in private context".to_string());
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
         }
 
         let mut contexts = Vec::new();
@@ -451,16 +1045,15 @@ impl MeroDocsState {
     // === SHARED CONTEXT METHODS ===
 
     /// Get detailed information about the shared context
-    pub fn get_context_details(&self, context_id: String) -> Result<ContextDetails, String> {
+    pub fn get_context_details(&self, context_id: String) -> Result<ContextDetails, MeroDocsError> {
         let mut participants_with_permissions = Vec::new();
 
         if let Ok(iter) = self.participants.iter() {
             for participant in iter {
-                let user_id_str = format!("{:?}", participant);
                 let permission = self
                     .permissions
-                    .get(&user_id_str)
-                    .map_err(|e| format!("Failed to get permission for user: {:?}", e))?
+                    .get(&participant)
+                    .map_err(|e| MeroDocsError::storage("Failed to get permission for user", e))?
                     .unwrap_or(PermissionLevel::Read);
 
                 participants_with_permissions.push(ParticipantInfo {
@@ -470,10 +1063,11 @@ impl MeroDocsState {
             }
         }
 
-        let document_count =
-            self.documents
-                .len()
-                .map_err(|e| format!("Failed to get document count: {:?}", e))? as u64;
+        let document_count = self
+            .documents
+            .len()
+            .map_err(|e| MeroDocsError::storage("Failed to get document count", e))?
+            as u64;
 
         let context_details = ContextDetails {
             context_id: context_id.clone(),
@@ -489,77 +1083,668 @@ impl MeroDocsState {
         Ok(context_details)
     }
 
-    fn validate_admin_permissions(&self) -> Result<(), String> {
+    /// Resolve the calling identity and ensure it is a participant of this shared context
+    fn validate_participant(&self) -> Result<(UserId, PermissionLevel), MeroDocsError> {
         if self.is_private {
-            return Err("This method can only be called from shared context".to_string());
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Shared,
+            });
         }
 
-        let current_user_str = format!("{:?}", self.owner);
-        match self.permissions.get(&current_user_str) {
-            Ok(Some(PermissionLevel::Admin)) => Ok(()),
-            Ok(Some(_)) => Err("Admin permissions required for this operation".to_string()),
-            Ok(None) => Err("User permissions not found".to_string()),
-            Err(e) => Err(format!("Failed to check user permissions: {:?}", e)),
+        let caller = current_user();
+        match self.permission_of(&caller)? {
+            Some(permission) => Ok((caller, permission)),
+            None => Err(MeroDocsError::NotParticipant { user_id: caller }),
         }
     }
 
-    /// Upload a document
-    pub fn upload_document(
-        &mut self,
-        context_id: String,
-        name: String,
-        hash: String,
-        pdf_blob_id_str: String,
-        file_size: u64,
-        embeddings: Option<Vec<f32>>,
-        extracted_text: Option<String>,
-        chunks: Option<Vec<DocumentChunk>>,
-    ) -> Result<String, String> {
-        let document_id = format!("doc_{}_{}", env::time_now(), name);
+    /// Resolve the calling identity and the invitation it received to this shared context
+    fn caller_invitation(&self) -> Result<(UserId, Invitation), MeroDocsError> {
+        if self.is_private {
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Shared,
+            });
+        }
 
-        if self.documents.contains(&document_id).unwrap_or(false) {
-            return Err("Document with this ID already exists".to_string());
+        let caller = current_user();
+        match self.pending_invitations.get(&caller) {
+            Ok(Some(invitation)) => Ok((caller, invitation)),
+            Ok(None) => Err(MeroDocsError::not_found(Resource::Invitation, caller)),
+            Err(e) => Err(MeroDocsError::storage("Failed to get invitation", e)),
         }
+    }
 
-        let pdf_blob_id_bytes = parse_blob_id_base58(&pdf_blob_id_str)?;
+    fn permission_of(&self, user_id: &UserId) -> Result<Option<PermissionLevel>, MeroDocsError> {
+        if !self
+            .participants
+            .contains(user_id)
+            .map_err(|e| MeroDocsError::storage("Failed to check participants", e))?
+        {
+            return Ok(None);
+        }
 
-        // Announce blob to the network for discovery
-        let current_context = env::context_id();
-        if env::blob_announce_to_context(&pdf_blob_id_bytes, &current_context) {
-            app::log!(
-                "Successfully announced PDF blob {} to network",
-                pdf_blob_id_str
-            );
-        } else {
-            app::log!("Failed to announce PDF blob {} to network", pdf_blob_id_str);
+        self.permissions
+            .get(user_id)
+            .map_err(|e| MeroDocsError::storage("Failed to check user permissions", e))
+    }
+
+    /// Resolve the calling participant and ensure their role grants `capability`
+    fn require(&self, capability: Capability) -> Result<UserId, MeroDocsError> {
+        let (caller, permission) = self.validate_participant()?;
+        if !permission.has(capability) {
+            return Err(MeroDocsError::PermissionDenied {
+                required: capability,
+            });
         }
+        Ok(caller)
+    }
 
-        let document = DocumentInfo {
-            id: document_id.clone(),
-            name: name.clone(),
-            hash,
-            uploaded_by: self.owner,
-            uploaded_at: env::time_now(),
-            status: DocumentStatus::Pending,
-            pdf_blob_id: pdf_blob_id_bytes,
-            size: file_size,
-            embeddings,
-            extracted_text,
-            chunks,
+    /// Validate an explicit signer list, or default to every participant allowed to sign
+    fn resolve_required_signers(
+        &self,
+        requested: Option<Vec<UserId>>,
+    ) -> Result<Vec<UserId>, MeroDocsError> {
+        let Some(requested) = requested else {
+            let mut signers = Vec::new();
+            if let Ok(iter) = self.participants.iter() {
+                for participant in iter {
+                    if self
+                        .permission_of(&participant)?
+                        .is_some_and(|permission| permission.has(Capability::Sign))
+                    {
+                        signers.push(participant);
+                    }
+                }
+            }
+            return Ok(signers);
         };
 
-        self.documents
-            .insert(document_id.clone(), document)
-            .map_err(|e| format!("Failed to upload document: {:?}", e))?;
+        if requested.is_empty() {
+            return Err(MeroDocsError::InvalidInput(
+                "At least one required signer must be specified".to_string(),
+            ));
+        }
 
-        self.document_signatures
-            .insert(document_id.clone(), Vector::new())
-            .map_err(|e| format!("Failed to initialize document signatures: {:?}", e))?;
+        let mut signers: Vec<UserId> = Vec::with_capacity(requested.len());
+        for signer in requested {
+            if signers.contains(&signer) {
+                return Err(MeroDocsError::InvalidInput(format!(
+                    "Duplicate required signer: {}",
+                    signer
+                )));
+            }
+            match self.permission_of(&signer)? {
+                Some(permission) if permission.has(Capability::Sign) => signers.push(signer),
+                Some(_) => {
+                    return Err(MeroDocsError::InvalidInput(format!(
+                        "Participant {} is not allowed to sign",
+                        signer
+                    )))
+                }
+                None => return Err(MeroDocsError::NotParticipant { user_id: signer }),
+            }
+        }
+        Ok(signers)
+    }
+
+    /// Distinct signers that have signed a document, in signing order
+    fn signed_signers(&self, document_id: &DocumentId) -> Result<Vec<UserId>, MeroDocsError> {
+        let mut signed = Vec::new();
+        let signatures = self
+            .document_signatures
+            .get(document_id)
+            .map_err(|e| MeroDocsError::storage("Failed to get document signatures", e))?;
+        if let Some(signatures) = signatures {
+            if let Ok(iter) = signatures.iter() {
+                for sig in iter {
+                    if !signed.contains(&sig.signer) {
+                        signed.push(sig.signer);
+                    }
+                }
+            }
+        }
+        Ok(signed)
+    }
+
+    fn build_signing_queue(&self, document: &DocumentInfo) -> Result<SigningQueue, MeroDocsError> {
+        let signed = self.signed_signers(&document.id)?;
+        let pending: Vec<UserId> = document
+            .required_signers
+            .iter()
+            .filter(|signer| !signed.contains(signer))
+            .copied()
+            .collect();
+        let next = match document.signing_mode {
+            SigningMode::Parallel => pending.clone(),
+            SigningMode::Sequential => pending.iter().take(1).copied().collect(),
+        };
+
+        Ok(SigningQueue {
+            document_id: document.id,
+            signing_mode: document.signing_mode,
+            signed,
+            pending,
+            next,
+        })
+    }
+
+    /// Record the active identity mapping of a joined context and index its shared identity
+    fn activate_identity_mapping(
+        &mut self,
+        metadata: &ContextMetadata,
+    ) -> Result<(), MeroDocsError> {
+        let identity_mapping = IdentityMapping {
+            private_identity: metadata.private_identity,
+            shared_identity: metadata.shared_identity,
+            context_id: metadata.context_id.clone(),
+            created_at: env::time_now(),
+            left_at: None,
+        };
+
+        self.identity_mappings
+            .insert(metadata.context_id.clone(), identity_mapping)
+            .map_err(|e| MeroDocsError::storage("Failed to store identity mapping", e))?;
+
+        self.shared_identity_index
+            .insert(metadata.shared_identity, metadata.context_id.clone())
+            .map_err(|e| MeroDocsError::storage("Failed to index shared identity", e))?;
+
+        Ok(())
+    }
+
+    /// Move the active identity mapping of a context, if any, to its archived history
+    fn archive_identity_mapping(&mut self, context_id: &str) -> Result<(), MeroDocsError> {
+        let Some(mut mapping) = self
+            .identity_mappings
+            .remove(context_id)
+            .map_err(|e| MeroDocsError::storage("Failed to remove identity mapping", e))?
+        else {
+            return Ok(());
+        };
+        mapping.left_at = Some(env::time_now());
+
+        // The identity may have been reused by a context joined later
+        let indexed = self
+            .shared_identity_index
+            .get(&mapping.shared_identity)
+            .map_err(|e| MeroDocsError::storage("Failed to get shared identity index", e))?;
+        if indexed.as_deref() == Some(context_id) {
+            self.shared_identity_index
+                .remove(&mapping.shared_identity)
+                .map_err(|e| MeroDocsError::storage("Failed to update shared identity index", e))?;
+        }
+
+        let mut history = self
+            .archived_identity_mappings
+            .get(context_id)
+            .map_err(|e| MeroDocsError::storage("Failed to get archived identity mappings", e))?
+            .unwrap_or_else(|| Vector::new());
+        history
+            .push(mapping)
+            .map_err(|e| MeroDocsError::storage("Failed to archive identity mapping", e))?;
+        self.archived_identity_mappings
+            .insert(context_id.to_owned(), history)
+            .map_err(|e| MeroDocsError::storage("Failed to archive identity mapping", e))?;
+
+        Ok(())
+    }
+
+    /// Unfinished documents still waiting for a user's signature.
+    ///
+    /// Collected before a user loses the right to sign, so a rejected change leaves no document
+    /// half-updated; a document the user is the only required signer of must be voided first.
+    fn documents_pending_on(&self, user_id: &UserId) -> Result<Vec<DocumentInfo>, MeroDocsError> {
+        let mut pending = Vec::new();
+        let documents = self
+            .documents
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to list documents", e))?;
+        for (document_id, document) in documents {
+            let unfinished =
+                document.status == DocumentStatus::Draft || document.status.is_open_for_signing();
+            if !unfinished || !document.required_signers.contains(user_id) {
+                continue;
+            }
+            if self.signed_signers(&document_id)?.contains(user_id) {
+                continue;
+            }
+            if document.required_signers.len() == 1 {
+                return Err(MeroDocsError::UpdateConflict(format!(
+                    "User {} is the only required signer of document {}; void it first",
+                    user_id, document_id
+                )));
+            }
+            pending.push(document);
+        }
+        Ok(pending)
+    }
+
+    /// Remove a user from the required signers of the given documents, completing any document
+    /// they were the last pending signer of
+    fn drop_pending_signer(
+        &mut self,
+        user_id: &UserId,
+        documents: Vec<DocumentInfo>,
+        actor: UserId,
+    ) -> Result<(), MeroDocsError> {
+        for mut document in documents {
+            let document_id = document.id;
+            let final_hash = document.hash;
+            document.required_signers.retain(|signer| signer != user_id);
+            if document.status.is_open_for_signing() {
+                document.status = self.build_signing_queue(&document)?.status();
+            }
+            let completed = document.status == DocumentStatus::FullySigned;
+
+            self.documents
+                .insert(document_id, document)
+                .map_err(|e| MeroDocsError::storage("Failed to update document", e))?;
+
+            if completed {
+                self.record_audit(
+                    actor,
+                    AuditAction::DocumentCompleted,
+                    Some(document_id),
+                    None,
+                    Some(final_hash),
+                    None,
+                )?;
+
+                app::emit!(MeroDocsEvent::DocumentCompleted {
+                    document_id,
+                    final_hash,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Earlier of the document's own deadline and the context deadline
+    fn deadline_of(&self, document_id: &DocumentId) -> Result<Option<u64>, MeroDocsError> {
+        let document_deadline = self
+            .document_deadlines
+            .get(document_id)
+            .map_err(|e| MeroDocsError::storage("Failed to get document deadline", e))?;
+
+        Ok(match (document_deadline, self.context_deadline) {
+            (Some(document), Some(context)) => Some(document.min(context)),
+            (document, context) => document.or(context),
+        })
+    }
+
+    fn ensure_before_deadline(&self, document_id: &DocumentId) -> Result<(), MeroDocsError> {
+        match self.deadline_of(document_id)? {
+            Some(deadline) if env::time_now() >= deadline => {
+                Err(MeroDocsError::DeadlinePassed { deadline })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Refuse changes once the context has expired
+    fn ensure_context_active(&self) -> Result<(), MeroDocsError> {
+        match self.context_status {
+            ContextStatus::Active => Ok(()),
+            ContextStatus::Expired => Err(MeroDocsError::ContextExpired),
+        }
+    }
+
+    /// Move a document along its lifecycle and record who moved it and why
+    fn transition_document(
+        &mut self,
+        document_id: DocumentId,
+        next: DocumentStatus,
+        actor: UserId,
+        action: AuditAction,
+        reason: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
+        };
+
+        let previous = document.status;
+        if !previous.can_transition_to(&next) {
+            return Err(MeroDocsError::InvalidStatusTransition {
+                from: previous,
+                to: next,
+            });
+        }
+
+        let document_hash = document.hash;
+        document.status = next;
+        self.documents
+            .insert(document_id, document)
+            .map_err(|e| MeroDocsError::storage("Failed to update document status", e))?;
+
+        self.record_audit(
+            actor,
+            action,
+            Some(document_id),
+            None,
+            Some(document_hash),
+            reason.clone(),
+        )?;
+
+        app::emit!(MeroDocsEvent::DocumentStatusChanged {
+            document_id,
+            from: previous,
+            to: next,
+            changed_by: actor,
+            reason,
+        });
+
+        Ok(())
+    }
+
+    /// Latest consent decision of a participant for a document, if any
+    fn latest_consent(
+        &self,
+        user_id: &UserId,
+        document_id: &DocumentId,
+    ) -> Result<Option<ConsentRecord>, MeroDocsError> {
+        let key = ConsentKey::for_document(user_id, document_id);
+        let Some(records) = self
+            .consents
+            .get(&key)
+            .map_err(|e| MeroDocsError::storage("Failed to check consent", e))?
+        else {
+            return Ok(None);
+        };
+
+        let len = records
+            .len()
+            .map_err(|e| MeroDocsError::storage("Failed to count consent records", e))?;
+        match len.checked_sub(1) {
+            Some(last) => records
+                .get(last)
+                .map_err(|e| MeroDocsError::storage("Failed to read consent record", e)),
+            None => Ok(None),
+        }
+    }
+
+    fn push_consent(
+        &mut self,
+        user_id: &UserId,
+        document_id: &DocumentId,
+        record: ConsentRecord,
+    ) -> Result<(), MeroDocsError> {
+        let key = ConsentKey::for_document(user_id, document_id);
+        let mut records = self
+            .consents
+            .get(&key)
+            .map_err(|e| MeroDocsError::storage("Failed to get consent records", e))?
+            .unwrap_or_else(|| Vector::new());
+
+        records
+            .push(record)
+            .map_err(|e| MeroDocsError::storage("Failed to store consent", e))?;
+
+        self.consents
+            .insert(key, records)
+            .map_err(|e| MeroDocsError::storage("Failed to update consent records", e))?;
+
+        Ok(())
+    }
+
+    /// Append a revision to a document's history and return its number
+    fn record_revision(
+        &mut self,
+        document_id: &DocumentId,
+        blob_id: [u8; 32],
+        hash: Sha256Digest,
+        size: u64,
+        author: UserId,
+        reason: RevisionReason,
+    ) -> Result<u64, MeroDocsError> {
+        let mut revisions = self
+            .document_revisions
+            .get(document_id)
+            .map_err(|e| MeroDocsError::storage("Failed to get document revisions", e))?
+            .unwrap_or_else(|| Vector::new());
+
+        let revision = revisions
+            .len()
+            .map_err(|e| MeroDocsError::storage("Failed to count document revisions", e))?
+            as u64;
+
+        revisions
+            .push(DocumentRevision {
+                revision,
+                blob_id,
+                hash,
+                size,
+                author,
+                created_at: env::time_now(),
+                reason,
+            })
+            .map_err(|e| MeroDocsError::storage("Failed to add document revision", e))?;
+
+        self.document_revisions
+            .insert(*document_id, revisions)
+            .map_err(|e| MeroDocsError::storage("Failed to update document revisions", e))?;
+
+        Ok(revision)
+    }
+
+    /// Derive a document ID from the context, uploader, content hash and a per-context counter
+    fn next_document_id(&mut self, uploader: &UserId, content_hash: &Sha256Digest) -> DocumentId {
+        let counter = self.document_count;
+        self.document_count += 1;
+
+        derive_document_id(uploader, content_hash, counter)
+    }
+
+    /// Append an entry to the shared context's audit log
+    fn record_audit(
+        &mut self,
+        actor: UserId,
+        action: AuditAction,
+        document_id: Option<DocumentId>,
+        subject: Option<UserId>,
+        document_hash: Option<Sha256Digest>,
+        details: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        let sequence = self
+            .audit_log
+            .len()
+            .map_err(|e| MeroDocsError::storage("Failed to count audit entries", e))?
+            as u64;
+
+        let previous_hash = match sequence.checked_sub(1) {
+            Some(last) => self
+                .audit_log
+                .get(last as usize)
+                .map_err(|e| MeroDocsError::storage("Failed to read audit entry", e))?
+                .map(|entry| entry.entry_hash),
+            None => None,
+        };
+
+        let mut entry = AuditEntry {
+            sequence,
+            actor,
+            action,
+            timestamp: env::time_now(),
+            document_id,
+            subject,
+            document_hash,
+            details,
+            previous_hash,
+            entry_hash: Sha256Digest::new([0; 32]),
+        };
+        entry.entry_hash = entry.compute_hash();
+
+        self.audit_log
+            .push(entry)
+            .map_err(|e| MeroDocsError::storage("Failed to append audit entry", e))
+    }
+
+    /// Recompute the audit log's hash chain and report the first broken link, if any
+    pub fn verify_audit_chain(&self) -> Result<AuditChainVerification, MeroDocsError> {
+        let _ = self.require(Capability::ViewAudit)?;
+
+        let mut verification = AuditChainVerification {
+            entries_checked: 0,
+            head_hash: None,
+            first_broken_link: None,
+        };
+
+        let entries = self
+            .audit_log
+            .iter()
+            .map_err(|e| MeroDocsError::storage("Failed to read audit log", e))?;
+        for entry in entries {
+            let intact = entry.sequence == verification.entries_checked
+                && entry.previous_hash == verification.head_hash
+                && entry.compute_hash() == entry.entry_hash;
+            if !intact && verification.first_broken_link.is_none() {
+                verification.first_broken_link = Some(verification.entries_checked);
+            }
+
+            verification.entries_checked += 1;
+            verification.head_hash = Some(entry.entry_hash);
+        }
+
+        Ok(verification)
+    }
+
+    /// Get a page of the audit log, oldest first
+    pub fn get_audit_log(&self, from: u64, limit: u64) -> Result<Vec<AuditEntry>, MeroDocsError> {
+        let _ = self.require(Capability::ViewAudit)?;
+
+        let len = self
+            .audit_log
+            .len()
+            .map_err(|e| MeroDocsError::storage("Failed to count audit entries", e))?
+            as u64;
+        let end = len.min(from.saturating_add(limit.min(MAX_AUDIT_PAGE_SIZE)));
+
+        let mut entries = Vec::new();
+        for index in from..end {
+            if let Some(entry) = self
+                .audit_log
+                .get(index as usize)
+                .map_err(|e| MeroDocsError::storage("Failed to read audit entry", e))?
+            {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Get every audit entry that refers to a document, oldest first
+    pub fn get_document_audit_log(
+        &self,
+        document_id: DocumentId,
+    ) -> Result<Vec<AuditEntry>, MeroDocsError> {
+        let _ = self.require(Capability::ViewAudit)?;
+
+        let mut entries = Vec::new();
+        if let Ok(iter) = self.audit_log.iter() {
+            for entry in iter {
+                if entry.document_id == Some(document_id) {
+                    entries.push(entry);
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Upload a document
+    pub fn upload_document(
+        &mut self,
+        context_id: String,
+        name: String,
+        hash: String,
+        pdf_blob_id_str: String,
+        file_size: u64,
+        embeddings: Option<Vec<f32>>,
+        extracted_text: Option<String>,
+        chunks: Option<Vec<DocumentChunk>>,
+        required_signers: Option<Vec<UserId>>,
+        signing_mode: Option<SigningMode>,
+        draft: Option<bool>,
+    ) -> Result<DocumentId, MeroDocsError> {
+        self.ensure_context_active()?;
+        let uploader = self.require(Capability::Upload)?;
+
+        let required_signers = self.resolve_required_signers(required_signers)?;
+
+        let pdf_blob_id_bytes = parse_blob_id_base58(&pdf_blob_id_str)?;
+        let hash = verify_blob(&pdf_blob_id_bytes, &hash, file_size)?;
+
+        let document_id = self.next_document_id(&uploader, &hash);
+
+        if self.documents.contains(&document_id).unwrap_or(false) {
+            return Err(MeroDocsError::already_exists(
+                Resource::Document,
+                document_id,
+            ));
+        }
+
+        // Announce blob to the network for discovery
+        let current_context = env::context_id();
+        if env::blob_announce_to_context(&pdf_blob_id_bytes, &current_context) {
+            app::log!(
+                "Successfully announced PDF blob {} to network",
+                pdf_blob_id_str
+            );
+        } else {
+            app::log!("Failed to announce PDF blob {} to network", pdf_blob_id_str);
+        }
+
+        let document = DocumentInfo {
+            id: document_id,
+            name: name.clone(),
+            hash,
+            uploaded_by: uploader,
+            uploaded_at: env::time_now(),
+            status: if draft.unwrap_or(false) {
+                DocumentStatus::Draft
+            } else {
+                DocumentStatus::Pending
+            },
+            pdf_blob_id: pdf_blob_id_bytes,
+            size: file_size,
+            embeddings,
+            extracted_text,
+            chunks,
+            required_signers,
+            signing_mode: signing_mode.unwrap_or(SigningMode::Parallel),
+        };
+
+        self.documents
+            .insert(document_id, document)
+            .map_err(|e| MeroDocsError::storage("Failed to upload document", e))?;
+
+        self.document_signatures
+            .insert(document_id, Vector::new())
+            .map_err(|e| MeroDocsError::storage("Failed to initialize document signatures", e))?;
+
+        self.record_revision(
+            &document_id,
+            pdf_blob_id_bytes,
+            hash,
+            file_size,
+            uploader,
+            RevisionReason::Upload,
+        )?;
+
+        self.record_audit(
+            uploader,
+            AuditAction::DocumentUploaded,
+            Some(document_id),
+            None,
+            Some(hash),
+            Some(name.clone()),
+        )?;
 
         app::emit!(MeroDocsEvent::DocumentUploaded {
-            id: document_id.clone(),
+            id: document_id,
             name,
-            uploaded_by: self.owner,
+            hash,
+            uploaded_by: uploader,
         });
 
         Ok(document_id)
@@ -569,25 +1754,234 @@ impl MeroDocsState {
     pub fn delete_document(
         &mut self,
         context_id: String,
-        document_id: String,
-    ) -> Result<(), String> {
-        self.validate_admin_permissions()?;
+        document_id: DocumentId,
+    ) -> Result<(), MeroDocsError> {
+        let caller = self.require(Capability::Delete)?;
 
         match self.documents.remove(&document_id) {
-            Ok(Some(_)) => {
+            Ok(Some(document)) => {
                 let _ = self.document_signatures.remove(&document_id);
+                let _ = self.document_revisions.remove(&document_id);
+                let _ = self.document_deadlines.remove(&document_id);
+
+                self.record_audit(
+                    caller,
+                    AuditAction::DocumentDeleted,
+                    Some(document_id),
+                    None,
+                    Some(document.hash),
+                    None,
+                )?;
 
                 app::emit!(MeroDocsEvent::DocumentDeleted { id: document_id });
 
                 Ok(())
             }
-            Ok(None) => Err(format!("Document not found: {}", document_id)),
-            Err(e) => Err(format!("Failed to delete document: {:?}", e)),
+            Ok(None) => Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => Err(MeroDocsError::storage("Failed to delete document", e)),
+        }
+    }
+
+    /// Replace the PDF of a document that has not been signed yet
+    pub fn amend_document(
+        &mut self,
+        context_id: String,
+        document_id: DocumentId,
+        pdf_blob_id_str: String,
+        file_size: u64,
+        new_hash: String,
+    ) -> Result<u64, MeroDocsError> {
+        self.ensure_context_active()?;
+        let (caller, caller_permission) = self.validate_participant()?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
+        };
+
+        if document.uploaded_by != caller && !caller_permission.has(Capability::ManageDocuments) {
+            return Err(MeroDocsError::Unauthorized(
+                "Only the uploader or an admin can amend this document".to_string(),
+            ));
+        }
+
+        if !matches!(
+            document.status,
+            DocumentStatus::Draft | DocumentStatus::Pending
+        ) {
+            return Err(MeroDocsError::UpdateConflict(format!(
+                "{:?} documents cannot be amended",
+                document.status
+            )));
+        }
+        if !self.signed_signers(&document_id)?.is_empty() {
+            return Err(MeroDocsError::UpdateConflict(
+                "Documents cannot be amended once signing has started".to_string(),
+            ));
+        }
+
+        let pdf_blob_id_bytes = parse_blob_id_base58(&pdf_blob_id_str)?;
+        let new_hash = verify_blob(&pdf_blob_id_bytes, &new_hash, file_size)?;
+
+        let current_context = env::context_id();
+        if !env::blob_announce_to_context(&pdf_blob_id_bytes, &current_context) {
+            app::log!(
+                "Failed to announce amended PDF blob {} to network",
+                pdf_blob_id_str
+            );
+        }
+
+        document.pdf_blob_id = pdf_blob_id_bytes;
+        document.size = file_size;
+        document.hash = new_hash;
+
+        self.documents
+            .insert(document_id, document)
+            .map_err(|e| MeroDocsError::storage("Failed to update document", e))?;
+
+        let revision = self.record_revision(
+            &document_id,
+            pdf_blob_id_bytes,
+            new_hash,
+            file_size,
+            caller,
+            RevisionReason::Amendment,
+        )?;
+
+        self.record_audit(
+            caller,
+            AuditAction::DocumentAmended,
+            Some(document_id),
+            None,
+            Some(new_hash),
+            Some(format!("Revision {}", revision)),
+        )?;
+
+        app::emit!(MeroDocsEvent::DocumentAmended {
+            document_id,
+            revision,
+        });
+
+        Ok(revision)
+    }
+
+    /// List every recorded revision of a document, oldest first
+    pub fn list_document_revisions(
+        &self,
+        document_id: DocumentId,
+    ) -> Result<Vec<DocumentRevision>, MeroDocsError> {
+        let mut revisions = Vec::new();
+        if let Ok(Some(history)) = self.document_revisions.get(&document_id) {
+            if let Ok(iter) = history.iter() {
+                for revision in iter {
+                    revisions.push(revision.clone());
+                }
+            }
+        }
+        Ok(revisions)
+    }
+
+    /// Find the document a v0 state stored under `legacy_id` (`doc_<timestamp>_<name>`)
+    pub fn find_document_by_legacy_id(
+        &self,
+        legacy_id: String,
+    ) -> Result<DocumentId, MeroDocsError> {
+        let histories = self
+            .document_revisions
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to list document revisions", e))?;
+        for (document_id, history) in histories {
+            let first = history
+                .get(0)
+                .map_err(|e| MeroDocsError::storage("Failed to get document revisions", e))?;
+            if let Some(DocumentRevision {
+                reason: RevisionReason::Migrated { legacy_id: id, .. },
+                ..
+            }) = first
+            {
+                if id == legacy_id {
+                    return Ok(document_id);
+                }
+            }
+        }
+        Err(MeroDocsError::not_found(Resource::Document, legacy_id))
+    }
+
+    /// Digest the blob of a migrated document whose legacy hash was not a SHA-256 digest, so it
+    /// can be signed
+    pub fn verify_migrated_document(
+        &mut self,
+        document_id: DocumentId,
+    ) -> Result<Sha256Digest, MeroDocsError> {
+        let caller = self.require(Capability::ManageDocuments)?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
+        };
+        if document.hash != Sha256Digest::UNVERIFIED {
+            return Err(MeroDocsError::UpdateConflict(format!(
+                "Document {} already has a verified hash",
+                document_id
+            )));
+        }
+
+        let (hash, size) = digest_blob(&document.pdf_blob_id)?;
+        if size != document.size {
+            return Err(MeroDocsError::SizeMismatch {
+                supplied: document.size,
+                computed: size,
+            });
+        }
+        document.hash = hash;
+
+        self.documents
+            .insert(document_id, document)
+            .map_err(|e| MeroDocsError::storage("Failed to update document", e))?;
+
+        self.record_audit(
+            caller,
+            AuditAction::DocumentAmended,
+            Some(document_id),
+            None,
+            Some(hash),
+            Some("Digest computed for migrated document".to_string()),
+        )?;
+
+        Ok(hash)
+    }
+
+    /// Get a specific revision of a document
+    pub fn get_document_revision(
+        &self,
+        document_id: DocumentId,
+        revision: u64,
+    ) -> Result<DocumentRevision, MeroDocsError> {
+        let history = match self.document_revisions.get(&document_id) {
+            Ok(Some(history)) => history,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => {
+                return Err(MeroDocsError::storage(
+                    "Failed to get document revisions",
+                    e,
+                ))
+            }
+        };
+
+        match history.get(revision as usize) {
+            Ok(Some(entry)) => Ok(entry),
+            Ok(None) => Err(MeroDocsError::not_found(
+                Resource::DocumentRevision,
+                format!("{}#{}", document_id, revision),
+            )),
+            Err(e) => Err(MeroDocsError::storage("Failed to get document revision", e)),
         }
     }
 
     /// List all documents
-    pub fn list_documents(&self, context_id: String) -> Result<Vec<DocumentInfo>, String> {
+    pub fn list_documents(&self, context_id: String) -> Result<Vec<DocumentInfo>, MeroDocsError> {
         let mut documents = Vec::new();
         if let Ok(entries) = self.documents.entries() {
             for (_, document) in entries {
@@ -597,45 +1991,241 @@ impl MeroDocsState {
         Ok(documents)
     }
 
-    /// In your set_consent and has_consented methods:
-    pub fn set_consent(&mut self, user_id: UserId, document_id: String) -> Result<(), String> {
-        let key = format!("{:?}|{}", user_id, document_id);
-        self.consents
-            .insert(key, true)
-            .map_err(|e| format!("Failed to store consent: {:?}", e))?;
+    /// Record the caller's consent to sign a document, with the disclosure text they accepted
+    pub fn set_consent(
+        &mut self,
+        document_id: DocumentId,
+        disclosure_version: Option<String>,
+        disclosure_hash: Option<Sha256Digest>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_context_active()?;
+        let user_id = self.require(Capability::Sign)?;
+
+        let document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
+        };
+        ensure_open_for_signing(&document)?;
+        self.ensure_before_deadline(&document_id)?;
+
+        let details = match (&disclosure_version, &disclosure_hash) {
+            (None, None) => None,
+            (version, hash) => Some(format!(
+                "Disclosure version {}, hash {}",
+                version.as_deref().unwrap_or("-"),
+                hash.map_or_else(|| "-".to_string(), |hash| hash.to_string())
+            )),
+        };
+        self.push_consent(
+            &user_id,
+            &document_id,
+            ConsentRecord {
+                given: true,
+                disclosure_version,
+                disclosure_hash,
+                recorded_at: env::time_now(),
+            },
+        )?;
+
+        self.record_audit(
+            user_id,
+            AuditAction::ConsentGiven,
+            Some(document_id),
+            Some(user_id),
+            Some(document.hash),
+            details,
+        )?;
+
+        app::emit!(MeroDocsEvent::ConsentGiven {
+            document_id,
+            user_id,
+            disclosure_hash,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw the caller's consent to a document they have not signed yet
+    pub fn withdraw_consent(&mut self, document_id: DocumentId) -> Result<(), MeroDocsError> {
+        self.ensure_context_active()?;
+        let user_id = self.require(Capability::Sign)?;
+
+        if !self.documents.contains(&document_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found(Resource::Document, document_id));
+        }
+
+        let consented = self
+            .latest_consent(&user_id, &document_id)?
+            .is_some_and(|record| record.given);
+        if !consented {
+            return Err(MeroDocsError::UpdateConflict(format!(
+                "User {} has not consented to this document",
+                user_id
+            )));
+        }
+        if self.signed_signers(&document_id)?.contains(&user_id) {
+            return Err(MeroDocsError::UpdateConflict(format!(
+                "User {} has already signed this document",
+                user_id
+            )));
+        }
+
+        self.push_consent(
+            &user_id,
+            &document_id,
+            ConsentRecord {
+                given: false,
+                disclosure_version: None,
+                disclosure_hash: None,
+                recorded_at: env::time_now(),
+            },
+        )?;
+
+        self.record_audit(
+            user_id,
+            AuditAction::ConsentWithdrawn,
+            Some(document_id),
+            Some(user_id),
+            None,
+            None,
+        )?;
+
+        app::emit!(MeroDocsEvent::ConsentWithdrawn {
+            document_id,
+            user_id,
+        });
+
         Ok(())
     }
 
-    /// Check if user has given consent for a document
-    pub fn has_consented(&self, user_id: UserId, document_id: String) -> Result<bool, String> {
-        let key = format!("{:?}|{}", user_id, document_id);
-        match self.consents.get(&key) {
-            Ok(Some(consented)) => Ok(consented),
-            Ok(None) => Ok(false),
-            Err(e) => Err(format!("Failed to check consent: {:?}", e)),
+    /// Check if the latest consent decision of a user for a document is a consent
+    pub fn has_consented(
+        &self,
+        user_id: UserId,
+        document_id: DocumentId,
+    ) -> Result<bool, MeroDocsError> {
+        Ok(self
+            .latest_consent(&user_id, &document_id)?
+            .is_some_and(|record| record.given))
+    }
+
+    /// Every consent decision of a user for a document, oldest first
+    pub fn get_consent_history(
+        &self,
+        user_id: UserId,
+        document_id: DocumentId,
+    ) -> Result<Vec<ConsentRecord>, MeroDocsError> {
+        let _ = self.validate_participant()?;
+
+        let key = ConsentKey::for_document(&user_id, &document_id);
+        let mut history = Vec::new();
+        if let Some(records) = self
+            .consents
+            .get(&key)
+            .map_err(|e| MeroDocsError::storage("Failed to get consent records", e))?
+        {
+            if let Ok(iter) = records.iter() {
+                history.extend(iter);
+            }
         }
+        Ok(history)
     }
+
+    /// Sign a document as the caller, or on behalf of another participant if the caller is an admin.
+    ///
+    /// A supplied `signature` must verify against the signer's identity; without one the
+    /// signature is recorded unverified and `verify_document_signatures` reports it as invalid.
     pub fn sign_document(
         &mut self,
         context_id: String,
-        document_id: String,
+        document_id: DocumentId,
         pdf_blob_id_str: String,
         file_size: u64,
         new_hash: String,
-        signer_id: UserId,
-    ) -> Result<(), String> {
-        let has_consent = self.has_consented(signer_id.clone(), document_id.clone())?;
+        signature: Option<Ed25519Signature>,
+        on_behalf_of: Option<UserId>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_context_active()?;
+        let (caller, caller_permission) = self.validate_participant()?;
+        if !caller_permission.has(Capability::Sign) {
+            return Err(MeroDocsError::PermissionDenied {
+                required: Capability::Sign,
+            });
+        }
+
+        let signer_id = match on_behalf_of {
+            Some(target) if target != caller => {
+                if !caller_permission.has(Capability::DelegateSigning) {
+                    return Err(MeroDocsError::PermissionDenied {
+                        required: Capability::DelegateSigning,
+                    });
+                }
+                match self.permission_of(&target)? {
+                    Some(permission) if permission.has(Capability::Sign) => target,
+                    Some(_) => {
+                        return Err(MeroDocsError::Unauthorized(format!(
+                            "Participant {} is not allowed to sign",
+                            target
+                        )))
+                    }
+                    None => return Err(MeroDocsError::NotParticipant { user_id: target }),
+                }
+            }
+            _ => caller,
+        };
+
+        let has_consent = self.has_consented(signer_id, document_id)?;
         if !has_consent {
-            return Err("User must provide consent before signing this document".to_string());
+            return Err(MeroDocsError::ConsentRequired {
+                user_id: signer_id,
+                document_id,
+            });
         }
 
         let mut document = match self.documents.get(&document_id) {
             Ok(Some(doc)) => doc,
-            Ok(None) => return Err("Document not found".to_string()),
-            Err(e) => return Err(format!("Failed to get document: {:?}", e)),
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
         };
 
+        ensure_open_for_signing(&document)?;
+        self.ensure_before_deadline(&document_id)?;
+        if document.hash == Sha256Digest::UNVERIFIED {
+            return Err(MeroDocsError::DocumentNotReady(format!(
+                "Document {} was migrated without a verifiable hash; verify it first",
+                document_id
+            )));
+        }
+
+        if !document.required_signers.contains(&signer_id) {
+            return Err(MeroDocsError::Unauthorized(format!(
+                "User {} is not a required signer of this document",
+                signer_id
+            )));
+        }
+
+        let mut queue = self.build_signing_queue(&document)?;
+        if queue.signed.contains(&signer_id) {
+            return Err(MeroDocsError::UpdateConflict(format!(
+                "User {} has already signed this document",
+                signer_id
+            )));
+        }
+        if document.signing_mode == SigningMode::Sequential && !queue.next.contains(&signer_id) {
+            return Err(MeroDocsError::NotSignersTurn { next: queue.next });
+        }
+
+        let approved_hash = document.hash;
+        if let Some(signature) = &signature {
+            let payload = signing_payload(&env::context_id(), &document_id, &approved_hash);
+            if !verify_ed25519(&signer_id, &payload, signature) {
+                return Err(MeroDocsError::InvalidSignature { signer: signer_id });
+            }
+        }
+
         let pdf_blob_id_bytes = parse_blob_id_base58(&pdf_blob_id_str)?;
+        let new_hash = verify_blob(&pdf_blob_id_bytes, &new_hash, file_size)?;
 
         // Announce the signed blob to the network for discovery
         let current_context = env::context_id();
@@ -650,63 +2240,357 @@ impl MeroDocsState {
                 pdf_blob_id_str
             );
         }
-
-        // Announce the signed blob to the network for discovery
-        let current_context = env::context_id();
-        if env::blob_announce_to_context(&pdf_blob_id_bytes, &current_context) {
-            app::log!(
-                "Successfully announced signed PDF blob {} to network",
-                pdf_blob_id_str
-            );
-        } else {
-            app::log!(
-                "Failed to announce signed PDF blob {} to network",
-                pdf_blob_id_str
-            );
+
+        document.pdf_blob_id = pdf_blob_id_bytes;
+        document.size = file_size;
+        document.hash = new_hash;
+
+        // Same completion rule as the registry: every required signer has signed
+        queue.signed.push(signer_id);
+        queue.pending.retain(|pending| *pending != signer_id);
+        document.status = queue.status();
+        let completed = document.status == DocumentStatus::FullySigned;
+
+        self.documents
+            .insert(document_id, document)
+            .map_err(|e| MeroDocsError::storage("Failed to update document", e))?;
+
+        self.record_revision(
+            &document_id,
+            pdf_blob_id_bytes,
+            new_hash,
+            file_size,
+            caller,
+            RevisionReason::Signature,
+        )?;
+
+        let signature = DocumentSignature {
+            signer: signer_id,
+            signed_by: caller,
+            signed_at: env::time_now(),
+            document_hash: approved_hash,
+            signature,
+        };
+
+        let mut signatures = self
+            .document_signatures
+            .get(&document_id)
+            .map_err(|e| MeroDocsError::storage("Failed to get document signatures", e))?
+            .unwrap_or_else(|| Vector::new());
+
+        signatures
+            .push(signature)
+            .map_err(|e| MeroDocsError::storage("Failed to add signature", e))?;
+
+        self.document_signatures
+            .insert(document_id, signatures)
+            .map_err(|e| MeroDocsError::storage("Failed to update document signatures", e))?;
+
+        self.record_audit(
+            caller,
+            AuditAction::SignatureApplied,
+            Some(document_id),
+            Some(signer_id),
+            Some(new_hash),
+            None,
+        )?;
+
+        app::emit!(MeroDocsEvent::DocumentSigned {
+            document_id,
+            signer: signer_id,
+            signed_by: caller,
+        });
+
+        if completed {
+            self.record_audit(
+                caller,
+                AuditAction::DocumentCompleted,
+                Some(document_id),
+                None,
+                Some(new_hash),
+                None,
+            )?;
+
+            app::emit!(MeroDocsEvent::DocumentCompleted {
+                document_id,
+                final_hash: new_hash,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Send a draft document out for signing
+    pub fn publish_document(
+        &mut self,
+        context_id: String,
+        document_id: DocumentId,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_context_active()?;
+        let (caller, caller_permission) = self.validate_participant()?;
+
+        let document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
+        };
+        if document.uploaded_by != caller && !caller_permission.has(Capability::ManageDocuments) {
+            return Err(MeroDocsError::Unauthorized(
+                "Only the uploader or an admin can publish this document".to_string(),
+            ));
+        }
+
+        self.transition_document(
+            document_id,
+            DocumentStatus::Pending,
+            caller,
+            AuditAction::DocumentPublished,
+            None,
+        )
+    }
+
+    /// Decline to sign a document, as one of its required signers who has not signed yet
+    pub fn decline_document(
+        &mut self,
+        context_id: String,
+        document_id: DocumentId,
+        reason: String,
+    ) -> Result<(), MeroDocsError> {
+        let caller = self.require(Capability::Sign)?;
+
+        if reason.trim().is_empty() {
+            return Err(MeroDocsError::InvalidInput(
+                "A reason is required to decline a document".to_string(),
+            ));
+        }
+
+        let document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
+        };
+        if !self
+            .build_signing_queue(&document)?
+            .pending
+            .contains(&caller)
+        {
+            return Err(MeroDocsError::Unauthorized(format!(
+                "User {} has no pending signature on this document",
+                caller
+            )));
+        }
+
+        self.transition_document(
+            document_id,
+            DocumentStatus::Declined,
+            caller,
+            AuditAction::DocumentDeclined,
+            Some(reason),
+        )
+    }
+
+    /// Withdraw a document that has not been fully signed
+    pub fn void_document(
+        &mut self,
+        context_id: String,
+        document_id: DocumentId,
+        reason: String,
+    ) -> Result<(), MeroDocsError> {
+        let caller = self.require(Capability::ManageDocuments)?;
+
+        if reason.trim().is_empty() {
+            return Err(MeroDocsError::InvalidInput(
+                "A reason is required to void a document".to_string(),
+            ));
+        }
+
+        self.transition_document(
+            document_id,
+            DocumentStatus::Voided,
+            caller,
+            AuditAction::DocumentVoided,
+            Some(reason),
+        )
+    }
+
+    /// Archive a document whose lifecycle has ended
+    pub fn archive_document(
+        &mut self,
+        context_id: String,
+        document_id: DocumentId,
+    ) -> Result<(), MeroDocsError> {
+        let caller = self.require(Capability::ManageDocuments)?;
+
+        self.transition_document(
+            document_id,
+            DocumentStatus::Archived,
+            caller,
+            AuditAction::DocumentArchived,
+            None,
+        )
+    }
+
+    /// Set or clear the signing deadline of the whole context
+    pub fn set_context_deadline(&mut self, deadline: Option<u64>) -> Result<(), MeroDocsError> {
+        self.ensure_context_active()?;
+        let caller = self.require(Capability::ManageDocuments)?;
+
+        validate_deadline(deadline)?;
+
+        self.context_deadline = deadline;
+
+        self.record_audit(
+            caller,
+            AuditAction::DeadlineSet,
+            None,
+            None,
+            None,
+            Some(describe_deadline(deadline)),
+        )
+    }
+
+    /// Set or clear the signing deadline of a document that is not signed yet
+    pub fn set_document_deadline(
+        &mut self,
+        context_id: String,
+        document_id: DocumentId,
+        deadline: Option<u64>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_context_active()?;
+        let (caller, caller_permission) = self.validate_participant()?;
+
+        let document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
+        };
+        if document.uploaded_by != caller && !caller_permission.has(Capability::ManageDocuments) {
+            return Err(MeroDocsError::Unauthorized(
+                "Only the uploader or an admin can change this document's deadline".to_string(),
+            ));
+        }
+        if document.status != DocumentStatus::Draft && !document.status.is_open_for_signing() {
+            return Err(MeroDocsError::DocumentNotReady(format!(
+                "Document {} is {:?}",
+                document_id, document.status
+            )));
+        }
+        validate_deadline(deadline)?;
+
+        match deadline {
+            Some(deadline) => self
+                .document_deadlines
+                .insert(document_id, deadline)
+                .map(|_| ()),
+            None => self.document_deadlines.remove(&document_id).map(|_| ()),
         }
+        .map_err(|e| MeroDocsError::storage("Failed to update document deadline", e))?;
+
+        self.record_audit(
+            caller,
+            AuditAction::DeadlineSet,
+            Some(document_id),
+            None,
+            Some(document.hash),
+            Some(describe_deadline(deadline)),
+        )
+    }
 
-        document.pdf_blob_id = pdf_blob_id_bytes;
-        document.size = file_size;
-        document.hash = new_hash;
-        document.status = DocumentStatus::PartiallySigned;
-
-        self.documents
-            .insert(document_id.clone(), document)
-            .map_err(|e| format!("Failed to update document: {:?}", e))?;
+    /// Move the context and every unsigned document whose deadline has passed to `Expired`
+    pub fn sweep_expired(&mut self) -> Result<SweepReport, MeroDocsError> {
+        let (caller, _) = self.validate_participant()?;
+        let now = env::time_now();
 
-        let signature = DocumentSignature {
-            signer: signer_id,
-            signed_at: env::time_now(),
+        let mut report = SweepReport {
+            context_expired: false,
+            expired_documents: Vec::new(),
         };
 
-        let mut signatures = self
-            .document_signatures
-            .get(&document_id)
-            .map_err(|e| format!("Failed to get document signatures: {:?}", e))?
-            .unwrap_or_else(|| Vector::new());
+        if self.context_status == ContextStatus::Active
+            && self
+                .context_deadline
+                .is_some_and(|deadline| now >= deadline)
+        {
+            self.context_status = ContextStatus::Expired;
+            self.record_audit(caller, AuditAction::ContextExpired, None, None, None, None)?;
+            report.context_expired = true;
+        }
 
-        signatures
-            .push(signature)
-            .map_err(|e| format!("Failed to add signature: {:?}", e))?;
+        let open_documents: Vec<DocumentId> = self
+            .documents
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to list documents", e))?
+            .filter(|(_, document)| document.status.is_open_for_signing())
+            .map(|(document_id, _)| document_id)
+            .collect();
+
+        for document_id in open_documents {
+            if self
+                .deadline_of(&document_id)?
+                .is_some_and(|deadline| now >= deadline)
+            {
+                self.transition_document(
+                    document_id,
+                    DocumentStatus::Expired,
+                    caller,
+                    AuditAction::DocumentExpired,
+                    None,
+                )?;
+                report.expired_documents.push(document_id);
+            }
+        }
 
-        self.document_signatures
-            .insert(document_id.clone(), signatures)
-            .map_err(|e| format!("Failed to update document signatures: {:?}", e))?;
+        Ok(report)
+    }
 
-        app::emit!(MeroDocsEvent::DocumentSigned {
-            document_id,
-            signer: signer_id,
-        });
+    /// List the context and unsigned documents whose deadline falls within the next
+    /// `within_seconds`, soonest first; overdue items not swept yet are included
+    pub fn list_expiring(&self, within_seconds: u64) -> Result<Vec<ExpiringItem>, MeroDocsError> {
+        let _ = self.validate_participant()?;
+        let horizon = env::time_now().saturating_add(within_seconds.saturating_mul(1_000_000_000));
+
+        let mut items = Vec::new();
+        if self.context_status == ContextStatus::Active {
+            if let Some(deadline) = self
+                .context_deadline
+                .filter(|deadline| *deadline <= horizon)
+            {
+                items.push(ExpiringItem {
+                    document_id: None,
+                    deadline,
+                });
+            }
+        }
 
-        Ok(())
+        let documents = self
+            .documents
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to list documents", e))?;
+        for (document_id, document) in documents {
+            if !document.status.is_open_for_signing() {
+                continue;
+            }
+            if let Some(deadline) = self
+                .deadline_of(&document_id)?
+                .filter(|deadline| *deadline <= horizon)
+            {
+                items.push(ExpiringItem {
+                    document_id: Some(document_id),
+                    deadline,
+                });
+            }
+        }
+
+        items.sort_by_key(|item| item.deadline);
+        Ok(items)
     }
 
     /// Get signatures for a document
     pub fn get_document_signatures(
         &self,
         context_id: String,
-        document_id: String,
-    ) -> Result<Vec<DocumentSignature>, String> {
+        document_id: DocumentId,
+    ) -> Result<Vec<DocumentSignature>, MeroDocsError> {
         let mut signatures = Vec::new();
         if let Ok(Some(sigs)) = self.document_signatures.get(&document_id) {
             if let Ok(iter) = sigs.iter() {
@@ -718,142 +2602,323 @@ impl MeroDocsState {
         Ok(signatures)
     }
 
-    /// Update document status to fully signed
+    /// Re-check every stored signature of a document against its signer's public key
+    pub fn verify_document_signatures(
+        &self,
+        document_id: DocumentId,
+    ) -> Result<Vec<SignatureVerification>, MeroDocsError> {
+        if !self.documents.contains(&document_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found(Resource::Document, document_id));
+        }
+
+        let context_id = env::context_id();
+        let mut results = Vec::new();
+        if let Ok(Some(sigs)) = self.document_signatures.get(&document_id) {
+            if let Ok(iter) = sigs.iter() {
+                for sig in iter {
+                    let payload = signing_payload(&context_id, &document_id, &sig.document_hash);
+                    results.push(SignatureVerification {
+                        signer: sig.signer,
+                        signed_at: sig.signed_at,
+                        valid: sig.signature.as_ref().is_some_and(|signature| {
+                            verify_ed25519(&sig.signer, &payload, signature)
+                        }),
+                        document_hash: sig.document_hash,
+                    });
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// Get the signing progress of a document and whose turn it is to sign
+    pub fn get_signing_queue(
+        &self,
+        document_id: DocumentId,
+    ) -> Result<SigningQueue, MeroDocsError> {
+        let document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
+        };
+
+        self.build_signing_queue(&document)
+    }
+
+    /// Recompute a document's status from its signatures after checking `user_id` has signed.
+    ///
+    /// `sign_document` already applies status transitions; this is a read-only consistency check.
     pub fn mark_participant_signed(
-        &mut self,
+        &self,
         context_id: String,
-        document_id: String,
+        document_id: DocumentId,
         user_id: UserId,
-    ) -> Result<(), String> {
-        let has_consent = self.has_consented(user_id.clone(), document_id.clone())?;
-        if !has_consent {
-            return Err("User must provide consent before being marked as signed".to_string());
-        }
+    ) -> Result<DocumentStatus, MeroDocsError> {
+        let _ = self.validate_participant()?;
 
-        let mut document = match self.documents.get(&document_id) {
+        let document = match self.documents.get(&document_id) {
             Ok(Some(doc)) => doc,
-            Ok(None) => return Err("Document not found".to_string()),
-            Err(e) => return Err(format!("Failed to get document: {:?}", e)),
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to get document", e)),
         };
 
-        let signatures = self
-            .document_signatures
-            .get(&document_id)
-            .map_err(|e| format!("Failed to get document signatures: {:?}", e))?
-            .unwrap_or_else(|| Vector::new());
-
-        let mut already_signed = false;
-        if let Ok(iter) = signatures.iter() {
-            for sig in iter {
-                if sig.signer == user_id {
-                    already_signed = true;
-                    break;
-                }
-            }
+        let queue = self.build_signing_queue(&document)?;
+        if !queue.signed.contains(&user_id) {
+            return Err(MeroDocsError::DocumentNotReady(format!(
+                "User {} has not signed this document yet",
+                user_id
+            )));
         }
-        if !already_signed {
-            return Err("User has not signed this document yet".to_string());
+
+        if document.status.is_open_for_signing() {
+            Ok(queue.status())
+        } else {
+            Ok(document.status)
         }
+    }
 
-        let mut all_signed = true;
-        if let Ok(participants_iter) = self.participants.iter() {
-            for participant in participants_iter {
-                let mut signed = false;
-                if let Ok(sig_iter) = signatures.iter() {
-                    for sig in sig_iter {
-                        if sig.signer == participant {
-                            signed = true;
-                            break;
-                        }
-                    }
-                }
-                if !signed {
-                    all_signed = false;
-                    break;
-                }
-            }
+    /// Remove participant from shared context; they are dropped from the signers still pending
+    /// on unfinished documents, as in `set_participant_permission`
+    pub fn remove_participant(
+        &mut self,
+        context_id: String,
+        user_id: UserId,
+    ) -> Result<(), MeroDocsError> {
+        let caller = self.require(Capability::Invite)?;
+
+        if user_id == self.owner {
+            return Err(MeroDocsError::Unauthorized(
+                "The context owner cannot be removed".to_string(),
+            ));
         }
 
-        if all_signed {
-            document.status = DocumentStatus::FullySigned;
-            self.documents
-                .insert(document_id.clone(), document)
-                .map_err(|e| format!("Failed to update document status: {:?}", e))?;
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::NotParticipant { user_id });
         }
+        let affected = self.documents_pending_on(&user_id)?;
+
+        self.participants
+            .remove(&user_id)
+            .map_err(|e| MeroDocsError::storage("Failed to remove participant", e))?;
+
+        self.permissions
+            .remove(&user_id)
+            .map_err(|e| MeroDocsError::storage("Failed to remove permissions", e))?;
+
+        self.record_audit(
+            caller,
+            AuditAction::ParticipantRemoved,
+            None,
+            Some(user_id),
+            None,
+            None,
+        )?;
+
+        app::emit!(MeroDocsEvent::ParticipantLeft { user_id });
+
+        self.drop_pending_signer(&user_id, affected, caller)?;
 
         Ok(())
     }
 
-    /// Add participant to shared context
-    pub fn add_participant(
+    /// Invite a user to the shared context; they become a participant once they accept
+    pub fn invite_participant(
         &mut self,
         context_id: String,
         user_id: UserId,
         permission: PermissionLevel,
-    ) -> Result<(), String> {
-        self.validate_admin_permissions()?;
+        expires_at: Option<u64>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_context_active()?;
+        let caller = self.require(Capability::Invite)?;
 
         if self.participants.contains(&user_id).unwrap_or(false) {
-            return Err("User is already a participant".to_string());
+            return Err(MeroDocsError::already_exists(
+                Resource::Participant,
+                user_id,
+            ));
+        }
+        let existing = self
+            .pending_invitations
+            .get(&user_id)
+            .map_err(|e| MeroDocsError::storage("Failed to get invitation", e))?;
+        if existing.is_some_and(|invitation| !invitation.is_expired(env::time_now())) {
+            return Err(MeroDocsError::already_exists(Resource::Invitation, user_id));
+        }
+        validate_deadline(expires_at)?;
+
+        let invitation = Invitation {
+            user_id,
+            role: permission.clone(),
+            invited_by: caller,
+            invited_at: env::time_now(),
+            expires_at,
+        };
+        self.pending_invitations
+            .insert(user_id, invitation)
+            .map_err(|e| MeroDocsError::storage("Failed to store invitation", e))?;
+
+        self.record_audit(
+            caller,
+            AuditAction::ParticipantInvited,
+            None,
+            Some(user_id),
+            None,
+            Some(format!("{:?}", permission)),
+        )?;
+
+        app::emit!(MeroDocsEvent::ParticipantInvited {
+            user_id,
+            role: permission,
+        });
+
+        Ok(())
+    }
+
+    /// Accept the caller's pending invitation and join the shared context with the offered role
+    pub fn accept_invitation(&mut self, context_id: String) -> Result<(), MeroDocsError> {
+        self.ensure_context_active()?;
+        let (user_id, invitation) = self.caller_invitation()?;
+
+        if invitation.is_expired(env::time_now()) {
+            return Err(MeroDocsError::DeadlinePassed {
+                deadline: invitation.expires_at.unwrap_or_default(),
+            });
         }
 
+        self.pending_invitations
+            .remove(&user_id)
+            .map_err(|e| MeroDocsError::storage("Failed to remove invitation", e))?;
+
         self.participants
             .insert(user_id)
-            .map_err(|e| format!("Failed to add participant: {:?}", e))?;
+            .map_err(|e| MeroDocsError::storage("Failed to add participant", e))?;
 
-        let user_id_str = format!("{:?}", user_id);
         self.permissions
-            .insert(user_id_str, permission.clone())
-            .map_err(|e| format!("Failed to set permissions: {:?}", e))?;
-
-        if permission == PermissionLevel::Sign {
-            let mut docs_to_update = Vec::new();
-            if let Ok(entries) = self.documents.entries() {
-                for (_, document) in entries {
-                    if document.status == DocumentStatus::FullySigned {
-                        let mut updated_document = document.clone();
-                        updated_document.status = DocumentStatus::PartiallySigned;
-                        docs_to_update.push(updated_document);
-                    }
-                }
-            }
-            for document in docs_to_update {
-                let _ = self.documents.insert(document.id.clone(), document);
-            }
-        }
+            .insert(user_id, invitation.role.clone())
+            .map_err(|e| MeroDocsError::storage("Failed to set permissions", e))?;
+
+        self.record_audit(
+            user_id,
+            AuditAction::InvitationAccepted,
+            None,
+            Some(user_id),
+            None,
+            Some(format!("{:?}", invitation.role)),
+        )?;
 
         app::emit!(MeroDocsEvent::ParticipantJoined { user_id });
 
         Ok(())
     }
-    /// Remove participant from shared context
-    pub fn remove_participant(
+
+    /// Decline the caller's pending invitation
+    pub fn decline_invitation(&mut self, context_id: String) -> Result<(), MeroDocsError> {
+        let (user_id, _) = self.caller_invitation()?;
+
+        self.pending_invitations
+            .remove(&user_id)
+            .map_err(|e| MeroDocsError::storage("Failed to remove invitation", e))?;
+
+        self.record_audit(
+            user_id,
+            AuditAction::InvitationDeclined,
+            None,
+            Some(user_id),
+            None,
+            None,
+        )?;
+
+        app::emit!(MeroDocsEvent::InvitationDeclined { user_id });
+
+        Ok(())
+    }
+
+    /// Invitations that have been neither accepted, declined nor left to expire
+    pub fn list_pending_invitations(
+        &self,
+        context_id: String,
+    ) -> Result<Vec<Invitation>, MeroDocsError> {
+        let _ = self.validate_participant()?;
+        let now = env::time_now();
+
+        let mut invitations = Vec::new();
+        if let Ok(entries) = self.pending_invitations.entries() {
+            for (_, invitation) in entries {
+                if !invitation.is_expired(now) {
+                    invitations.push(invitation);
+                }
+            }
+        }
+        Ok(invitations)
+    }
+
+    /// Change the role of an existing participant.
+    ///
+    /// Signing requirements follow the new role: a participant who can no longer sign is dropped
+    /// from the signers still pending on unfinished documents, completing any document they were
+    /// the last one pending on, while signatures they already gave stay valid. A participant who
+    /// gains the right to sign is only picked up by documents uploaded afterwards.
+    pub fn set_participant_permission(
         &mut self,
         context_id: String,
         user_id: UserId,
-    ) -> Result<(), String> {
-        self.validate_admin_permissions()?;
+        permission: PermissionLevel,
+    ) -> Result<(), MeroDocsError> {
+        let caller = self.require(Capability::ManagePermissions)?;
 
-        if !self.participants.contains(&user_id).unwrap_or(false) {
-            return Err("User is not a participant".to_string());
+        let Some(previous) = self.permission_of(&user_id)? else {
+            return Err(MeroDocsError::NotParticipant { user_id });
+        };
+        if user_id == self.owner && permission != PermissionLevel::Admin {
+            return Err(MeroDocsError::Unauthorized(
+                "The context owner must remain an admin".to_string(),
+            ));
+        }
+        if previous == permission {
+            return Ok(());
         }
 
-        self.participants
-            .remove(&user_id)
-            .map_err(|e| format!("Failed to remove participant: {:?}", e))?;
+        let affected = if permission.has(Capability::Sign) {
+            Vec::new()
+        } else {
+            self.documents_pending_on(&user_id)?
+        };
 
-        let user_id_str = format!("{:?}", user_id);
         self.permissions
-            .remove(&user_id_str)
-            .map_err(|e| format!("Failed to remove permissions: {:?}", e))?;
+            .insert(user_id, permission.clone())
+            .map_err(|e| MeroDocsError::storage("Failed to set permissions", e))?;
+
+        let mut details = format!("{:?} -> {:?}", previous, permission);
+        if !affected.is_empty() {
+            details.push_str(&format!(
+                "; removed as pending signer from {} document(s)",
+                affected.len()
+            ));
+        }
+        self.record_audit(
+            caller,
+            AuditAction::PermissionChanged,
+            None,
+            Some(user_id),
+            None,
+            Some(details),
+        )?;
+
+        app::emit!(MeroDocsEvent::PermissionChanged {
+            user_id,
+            from: previous,
+            to: permission,
+            changed_by: caller,
+        });
 
-        app::emit!(MeroDocsEvent::ParticipantLeft { user_id });
+        self.drop_pending_signer(&user_id, affected, caller)?;
 
         Ok(())
     }
 
     /// List all participants
-    pub fn list_participants(&self, context_id: String) -> Result<Vec<UserId>, String> {
+    pub fn list_participants(&self, context_id: String) -> Result<Vec<UserId>, MeroDocsError> {
         let mut participants = Vec::new();
         if let Ok(iter) = self.participants.iter() {
             for participant in iter {
@@ -863,46 +2928,198 @@ impl MeroDocsState {
         Ok(participants)
     }
 
+    /// Membership of a user in this shared context, to be passed to `sync_joined_contexts`
+    /// in their private context
+    pub fn get_membership(
+        &self,
+        context_id: String,
+        user_id: UserId,
+    ) -> Result<MembershipSnapshot, MeroDocsError> {
+        if self.is_private {
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Shared,
+            });
+        }
+
+        let role = self.permission_of(&user_id)?;
+        let document_count = match role {
+            Some(_) => self
+                .documents
+                .len()
+                .map_err(|e| MeroDocsError::storage("Failed to get document count", e))?
+                as u64,
+            None => 0,
+        };
+
+        Ok(MembershipSnapshot {
+            context_id,
+            user_id,
+            role,
+            document_count,
+        })
+    }
+
     /// Get user permission level
     pub fn get_user_permission(
         &self,
         context_id: String,
         user_id: UserId,
-    ) -> Result<PermissionLevel, String> {
-        let user_id_str = format!("{:?}", user_id);
-        match self.permissions.get(&user_id_str) {
+    ) -> Result<PermissionLevel, MeroDocsError> {
+        match self.permissions.get(&user_id) {
             Ok(Some(perm)) => Ok(perm.clone()),
-            Ok(None) => Err("User not found".to_string()),
-            Err(e) => Err(format!("Failed to get permission: {:?}", e)),
+            Ok(None) => Err(MeroDocsError::not_found(Resource::Participant, user_id)),
+            Err(e) => Err(MeroDocsError::storage("Failed to get permission", e)),
+        }
+    }
+
+    /// Capabilities granted to a participant by their role
+    pub fn get_user_capabilities(
+        &self,
+        context_id: String,
+        user_id: UserId,
+    ) -> Result<Vec<Capability>, MeroDocsError> {
+        match self.permission_of(&user_id)? {
+            Some(permission) => Ok(permission.capabilities().to_vec()),
+            None => Err(MeroDocsError::not_found(Resource::Participant, user_id)),
         }
     }
 
     /// Get current context ID
     pub fn get_context_id(&self) -> String {
         if self.is_private {
-            format!("private_{}", format!("{:?}", self.owner))
+            format!("private_{}", self.owner)
         } else {
             self.context_name.clone()
         }
     }
 
+    /// Refresh joined contexts from membership snapshots taken in each shared context.
+    ///
+    /// Contexts the user no longer belongs to are marked `Removed` and their identity mapping is
+    /// archived, so the dashboard never offers an identity the shared context would reject; a
+    /// context that re-added the user becomes `Active` again with a fresh mapping.
+    pub fn sync_joined_contexts(
+        &mut self,
+        snapshots: Vec<MembershipSnapshot>,
+    ) -> Result<SyncReport, MeroDocsError> {
+        if !self.is_private {
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
+        }
+
+        let now = env::time_now();
+        let mut report = SyncReport {
+            updated: Vec::new(),
+            removed: Vec::new(),
+        };
+
+        for snapshot in snapshots {
+            let mut metadata = match self.joined_contexts.get(&snapshot.context_id) {
+                Ok(Some(metadata)) => metadata,
+                Ok(None) => {
+                    return Err(MeroDocsError::not_found(
+                        Resource::JoinedContext,
+                        snapshot.context_id,
+                    ))
+                }
+                Err(e) => return Err(MeroDocsError::storage("Failed to get joined context", e)),
+            };
+            if snapshot.user_id != metadata.shared_identity {
+                return Err(MeroDocsError::InvalidInput(format!(
+                    "Snapshot for context {} is not for identity {}",
+                    snapshot.context_id, metadata.shared_identity
+                )));
+            }
+
+            metadata.synced_at = Some(now);
+            match snapshot.role {
+                Some(role) => {
+                    // Re-added after a removal: the mapping archived then is needed again
+                    if metadata.membership == MembershipStatus::Removed {
+                        self.activate_identity_mapping(&metadata)?;
+                    }
+                    metadata.role = Some(role);
+                    metadata.document_count = Some(snapshot.document_count);
+                    metadata.membership = MembershipStatus::Active;
+                    report.updated.push(snapshot.context_id.clone());
+                }
+                None => {
+                    let removed = metadata.membership == MembershipStatus::Active;
+                    metadata.role = None;
+                    metadata.document_count = None;
+                    metadata.membership = MembershipStatus::Removed;
+                    self.archive_identity_mapping(&snapshot.context_id)?;
+                    if removed {
+                        report.removed.push(snapshot.context_id.clone());
+                    }
+                }
+            }
+
+            self.joined_contexts
+                .insert(snapshot.context_id, metadata)
+                .map_err(|e| MeroDocsError::storage("Failed to update joined context", e))?;
+        }
+
+        Ok(report)
+    }
+
+    /// Identity mappings of joined contexts, optionally followed by those of contexts left
+    pub fn list_identity_mappings(
+        &self,
+        include_inactive: bool,
+    ) -> Result<Vec<IdentityMapping>, MeroDocsError> {
+        if !self.is_private {
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
+        }
+
+        let mut mappings = Vec::new();
+        if let Ok(entries) = self.identity_mappings.entries() {
+            for (_, mapping) in entries {
+                mappings.push(mapping);
+            }
+        }
+        if include_inactive {
+            if let Ok(entries) = self.archived_identity_mappings.entries() {
+                for (_, history) in entries {
+                    if let Ok(iter) = history.iter() {
+                        mappings.extend(iter);
+                    }
+                }
+            }
+        }
+        Ok(mappings)
+    }
+
     /// Get identity mapping for a specific context
-    pub fn get_identity_mapping(&self, context_id: String) -> Result<IdentityMapping, String> {
+    pub fn get_identity_mapping(
+        &self,
+        context_id: String,
+    ) -> Result<IdentityMapping, MeroDocsError> {
         if !self.is_private {
-            return Err("Identity mappings can only be accessed in private context".to_string());
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
         }
 
         match self.identity_mappings.get(&context_id) {
             Ok(Some(mapping)) => Ok(mapping.clone()),
-            Ok(None) => Err("Identity mapping not found for this context".to_string()),
-            Err(e) => Err(format!("Failed to get identity mapping: {:?}", e)),
+            Ok(None) => Err(MeroDocsError::not_found(
+                Resource::IdentityMapping,
+                context_id,
+            )),
+            Err(e) => Err(MeroDocsError::storage("Failed to get identity mapping", e)),
         }
     }
 
     /// Get shared identity for a specific context
-    pub fn get_shared_identity(&self, context_id: String) -> Result<UserId, String> {
+    pub fn get_shared_identity(&self, context_id: String) -> Result<UserId, MeroDocsError> {
         if !self.is_private {
-            return Err("Identity resolution can only be done in private context".to_string());
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
         }
 
         let mapping = self.get_identity_mapping(context_id)?;
@@ -913,43 +3130,62 @@ impl MeroDocsState {
     pub fn resolve_private_identity(
         &self,
         shared_identity: UserId,
-    ) -> Result<Option<UserId>, String> {
-        if self.is_private {
-            if let Ok(entries) = self.identity_mappings.entries() {
-                for (_, mapping) in entries {
-                    if mapping.shared_identity == shared_identity {
-                        return Ok(Some(mapping.private_identity));
-                    }
-                }
-            }
-            Ok(None)
-        } else {
-            Err("Cannot resolve private identity from shared context".to_string())
+    ) -> Result<Option<UserId>, MeroDocsError> {
+        if !self.is_private {
+            return Err(MeroDocsError::WrongContextType {
+                expected: ContextKind::Private,
+            });
         }
+
+        let Some(context_id) = self
+            .shared_identity_index
+            .get(&shared_identity)
+            .map_err(|e| MeroDocsError::storage("Failed to get shared identity index", e))?
+        else {
+            return Ok(None);
+        };
+
+        match self.identity_mappings.get(&context_id) {
+            Ok(mapping) => Ok(mapping.map(|mapping| mapping.private_identity)),
+            Err(e) => Err(MeroDocsError::storage("Failed to get identity mapping", e)),
+        }
+    }
+
+    /// Resolve several shared identities at once; results are in the order of `shared_identities`
+    pub fn resolve_private_identities(
+        &self,
+        shared_identities: Vec<UserId>,
+    ) -> Result<Vec<Option<UserId>>, MeroDocsError> {
+        shared_identities
+            .into_iter()
+            .map(|shared_identity| self.resolve_private_identity(shared_identity))
+            .collect()
     }
 
     pub fn search_document_by_embedding(
         &self,
         query_embedding: Vec<f32>,
-        document_id: String,
-    ) -> Result<String, String> {
+        document_id: DocumentId,
+    ) -> Result<String, MeroDocsError> {
         let document = match self.documents.get(&document_id) {
             Ok(Some(doc)) => doc,
-            Ok(None) => return Err(format!("Document with ID '{}' not found", document_id)),
-            Err(e) => return Err(format!("Failed to access document: {:?}", e)),
+            Ok(None) => return Err(MeroDocsError::not_found(Resource::Document, document_id)),
+            Err(e) => return Err(MeroDocsError::storage("Failed to access document", e)),
         };
 
         if let Some(chunks) = &document.chunks {
             if chunks.is_empty() {
-                return Err("Document has no chunks for semantic search".to_string());
+                return Err(MeroDocsError::DocumentNotReady(
+                    "Document has no chunks for semantic search".to_string(),
+                ));
             }
 
             if chunks[0].embedding.len() != query_embedding.len() {
-                return Err(format!(
+                return Err(MeroDocsError::InvalidInput(format!(
                     "Embedding dimension mismatch: query={}, document chunks={}",
                     query_embedding.len(),
                     chunks[0].embedding.len()
-                ));
+                )));
             }
 
             let mut chunk_similarities: Vec<(&DocumentChunk, f32)> = chunks
@@ -1009,15 +3245,19 @@ impl MeroDocsState {
 
         let doc_embedding = match &document.embeddings {
             Some(embedding) => embedding,
-            None => return Err("Document has no embeddings for semantic search".to_string()),
+            None => {
+                return Err(MeroDocsError::DocumentNotReady(
+                    "Document has no embeddings for semantic search".to_string(),
+                ))
+            }
         };
 
         if doc_embedding.len() != query_embedding.len() {
-            return Err(format!(
+            return Err(MeroDocsError::InvalidInput(format!(
                 "Embedding dimension mismatch: query={}, document={}",
                 query_embedding.len(),
                 doc_embedding.len()
-            ));
+            )));
         }
 
         let similarity = cosine_similarity(&query_embedding, doc_embedding);
@@ -1059,6 +3299,123 @@ impl MeroDocsState {
     }
 }
 
+fn derive_document_id(uploader: &UserId, content_hash: &Sha256Digest, counter: u64) -> DocumentId {
+    let mut hasher = Sha256::new();
+    hasher.update(env::context_id());
+    hasher.update(uploader);
+    hasher.update(content_hash);
+    hasher.update(counter.to_le_bytes());
+
+    DocumentId::new(hasher.finalize().into())
+}
+
+/// Message a signer signs to approve a document version.
+///
+/// This is the Borsh encoding of `(context_id, document_id, document_hash)`, i.e. the raw
+/// 32-byte context ID, document ID and SHA-256 digest concatenated.
+fn signing_payload(
+    context_id: &[u8; 32],
+    document_id: &DocumentId,
+    document_hash: &Sha256Digest,
+) -> Vec<u8> {
+    calimero_sdk::borsh::to_vec(&(context_id, document_id, document_hash))
+        .expect("serializing the signing payload cannot fail")
+}
+
+fn verify_ed25519(signer: &UserId, message: &[u8], signature: &Ed25519Signature) -> bool {
+    let Ok(public_key) = VerifyingKey::from_bytes(signer) else {
+        return false;
+    };
+
+    public_key
+        .verify_strict(message, &Signature::from_bytes(signature))
+        .is_ok()
+}
+
+/// Stream a blob through the Calimero blob API, returning its SHA-256 digest and size
+fn digest_blob(blob_id: &[u8; 32]) -> Result<(Sha256Digest, u64), MeroDocsError> {
+    let fd = env::blob_open(blob_id);
+    if fd == 0 {
+        return Err(MeroDocsError::BlobUnavailable {
+            blob_id: encode_blob_id_base58(blob_id),
+        });
+    }
+
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; BLOB_READ_CHUNK_SIZE];
+    let mut size = 0u64;
+    loop {
+        let read = env::blob_read(fd, &mut buffer) as usize;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+    }
+    let _ = env::blob_close(fd);
+
+    Ok((Sha256Digest::new(hasher.finalize().into()), size))
+}
+
+/// Hash a blob through the Calimero blob API and check it against the caller-supplied digest and size
+fn verify_blob(
+    blob_id: &[u8; 32],
+    expected_hash: &str,
+    expected_size: u64,
+) -> Result<Sha256Digest, MeroDocsError> {
+    let expected_hash: Sha256Digest = expected_hash.parse().map_err(|e| {
+        MeroDocsError::InvalidInput(format!("Invalid SHA-256 hash '{}': {}", expected_hash, e))
+    })?;
+
+    let (computed, size) = digest_blob(blob_id)?;
+    if computed != expected_hash {
+        return Err(MeroDocsError::HashMismatch {
+            supplied: expected_hash,
+            computed,
+        });
+    }
+    if size != expected_size {
+        return Err(MeroDocsError::SizeMismatch {
+            supplied: expected_size,
+            computed: size,
+        });
+    }
+
+    Ok(computed)
+}
+
+fn validate_deadline(deadline: Option<u64>) -> Result<(), MeroDocsError> {
+    match deadline {
+        Some(deadline) if deadline <= env::time_now() => Err(MeroDocsError::InvalidInput(
+            "Deadline must be in the future".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn describe_deadline(deadline: Option<u64>) -> String {
+    match deadline {
+        Some(deadline) => format!("Deadline set to {}", deadline),
+        None => "Deadline cleared".to_string(),
+    }
+}
+
+fn ensure_open_for_signing(document: &DocumentInfo) -> Result<(), MeroDocsError> {
+    if document.status.is_open_for_signing() {
+        Ok(())
+    } else {
+        Err(MeroDocsError::DocumentNotReady(format!(
+            "Document {} is {:?}",
+            document.id, document.status
+        )))
+    }
+}
+
+/// Identity executing the current call
+fn current_user() -> UserId {
+    UserId::new(env::executor_id())
+}
+
 fn cosine_similarity(a: &Vec<f32>, b: &Vec<f32>) -> f32 {
     let dot_product: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
     let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
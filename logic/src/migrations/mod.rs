@@ -0,0 +1,131 @@
+//! Upgrade path for contexts created by earlier releases of the app.
+//!
+//! Every layout change bumps [`SCHEMA_VERSION`] and adds a module holding the previous layout
+//! with a `migrate` step to the next one, so any older state is upgraded one version at a time.
+
+use calimero_sdk::borsh::BorshDeserialize;
+use calimero_sdk::state::read_raw;
+use calimero_sdk::{app, env};
+
+use crate::{MeroDocsError, MeroDocsState};
+
+mod v0;
+mod v1;
+mod v10;
+mod v11;
+mod v2;
+mod v3;
+mod v4;
+mod v5;
+mod v6;
+mod v7;
+mod v8;
+mod v9;
+
+#[cfg(test)]
+mod tests;
+
+/// Schema version of the state layout written by this build
+pub const SCHEMA_VERSION: u32 = 12;
+
+/// First schema version that stores `schema_version` as the leading field of the state
+const FIRST_VERSIONED_SCHEMA: u32 = 2;
+
+/// Upgrade the stored state of an existing context to the current layout
+#[app::migrate]
+pub fn migrate() -> MeroDocsState {
+    let Some(bytes) = read_raw() else {
+        env::panic_str("No existing state found to migrate");
+    };
+
+    upgrade(&bytes).unwrap_or_else(|e| env::panic_str(&format!("State migration failed: {}", e)))
+}
+
+/// Deserialize a stored state of any known version and migrate it step by step
+pub fn upgrade(bytes: &[u8]) -> Result<MeroDocsState, MeroDocsError> {
+    match detect_version(bytes)? {
+        0 => load::<v0::MeroDocsStateV0>(bytes)?.upgrade(),
+        1 => load::<v1::MeroDocsStateV1>(bytes)?.upgrade(),
+        2 => load::<v2::MeroDocsStateV2>(bytes)?.upgrade(),
+        3 => load::<v3::MeroDocsStateV3>(bytes)?.upgrade(),
+        4 => load::<v4::MeroDocsStateV4>(bytes)?.upgrade(),
+        5 => load::<v5::MeroDocsStateV5>(bytes)?.upgrade(),
+        6 => load::<v6::MeroDocsStateV6>(bytes)?.upgrade(),
+        7 => load::<v7::MeroDocsStateV7>(bytes)?.upgrade(),
+        8 => load::<v8::MeroDocsStateV8>(bytes)?.upgrade(),
+        9 => load::<v9::MeroDocsStateV9>(bytes)?.upgrade(),
+        10 => load::<v10::MeroDocsStateV10>(bytes)?.upgrade(),
+        11 => load::<v11::MeroDocsStateV11>(bytes)?.upgrade(),
+        SCHEMA_VERSION => load::<MeroDocsState>(bytes),
+        version => Err(MeroDocsError::StorageError(format!(
+            "Unsupported schema version {}",
+            version
+        ))),
+    }
+}
+
+/// Migration from a stored layout all the way to the current one
+trait Upgrade {
+    fn upgrade(self) -> Result<MeroDocsState, MeroDocsError>;
+}
+
+impl Upgrade for MeroDocsState {
+    fn upgrade(self) -> Result<MeroDocsState, MeroDocsError> {
+        Ok(self)
+    }
+}
+
+/// Upgrade each layout by migrating it one version and upgrading the result
+macro_rules! upgrade_by_migrating {
+    ($($state:ty),* $(,)?) => {
+        $(
+            impl Upgrade for $state {
+                fn upgrade(self) -> Result<MeroDocsState, MeroDocsError> {
+                    self.migrate()?.upgrade()
+                }
+            }
+        )*
+    };
+}
+
+upgrade_by_migrating!(
+    v0::MeroDocsStateV0,
+    v1::MeroDocsStateV1,
+    v2::MeroDocsStateV2,
+    v3::MeroDocsStateV3,
+    v4::MeroDocsStateV4,
+    v5::MeroDocsStateV5,
+    v6::MeroDocsStateV6,
+    v7::MeroDocsStateV7,
+    v8::MeroDocsStateV8,
+    v9::MeroDocsStateV9,
+    v10::MeroDocsStateV10,
+    v11::MeroDocsStateV11,
+);
+
+/// Versioned layouts lead with a little-endian `u32` version. Unversioned layouts lead with the
+/// `is_private` flag, whose first byte is 0 or 1, so they never read as a version of 2 to 255;
+/// those are told apart by which layout consumes the whole buffer.
+fn detect_version(bytes: &[u8]) -> Result<u32, MeroDocsError> {
+    if let Some(header) = bytes.get(..4) {
+        let version = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        if (FIRST_VERSIONED_SCHEMA..=u32::from(u8::MAX)).contains(&version) {
+            return Ok(version);
+        }
+    }
+
+    if v1::MeroDocsStateV1::try_from_slice(bytes).is_ok() {
+        return Ok(1);
+    }
+    if v0::MeroDocsStateV0::try_from_slice(bytes).is_ok() {
+        return Ok(0);
+    }
+
+    Err(MeroDocsError::StorageError(
+        "Unrecognized state layout".to_string(),
+    ))
+}
+
+fn load<T: BorshDeserialize>(bytes: &[u8]) -> Result<T, MeroDocsError> {
+    T::try_from_slice(bytes).map_err(|e| MeroDocsError::storage("Failed to deserialize state", e))
+}
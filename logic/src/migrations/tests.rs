@@ -0,0 +1,960 @@
+//! Fixtures of earlier layouts, upgraded through the full migration ladder.
+//!
+//! Fixtures are built from the frozen `vN` types and serialized the way an older release
+//! stored them, so a change to a frozen type or a `migrate` step shows up here.
+
+use calimero_sdk::borsh::to_vec;
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use super::v0::{DocumentInfoV0, DocumentSignatureV0, MeroDocsStateV0};
+use super::v1::MeroDocsStateV1;
+use super::v10::MeroDocsStateV10;
+use super::v11::{
+    AuditEntryV11, ConsentRecordV11, ContextMetadataV11, DocumentChunkV11, DocumentInfoV11,
+    DocumentRevisionV11, DocumentSignatureV11, IdentityMappingV11, InvitationV11, MeroDocsStateV11,
+    RevisionReasonV11, SignatureRecordV11,
+};
+use super::v2::MeroDocsStateV2;
+use super::v3::{AuditEntryV3, MeroDocsStateV3};
+use super::v4::MeroDocsStateV4;
+use super::v5::MeroDocsStateV5;
+use super::v6::{ContextMetadataV6, MeroDocsStateV6, ParticipantRoleV6};
+use super::v7::MeroDocsStateV7;
+use super::v8::{ContextMetadataV8, MeroDocsStateV8};
+use super::v9::{IdentityMappingV9, MeroDocsStateV9};
+use super::{detect_version, upgrade, SCHEMA_VERSION};
+use crate::types::hash::Sha256Digest;
+use crate::types::id::{ConsentKey, DocumentId, Ed25519Signature, UserId};
+use crate::{
+    AuditAction, ContextStatus, DocumentStatus, MembershipStatus, MeroDocsError, PermissionLevel,
+    RevisionReason, SignatureKind, SigningMode,
+};
+
+const OWNER: UserId = UserId::new([1; 32]);
+const SIGNER: UserId = UserId::new([2; 32]);
+const READER: UserId = UserId::new([3; 32]);
+
+const LEGACY_ID: &str = "doc_1700000000_contract.pdf";
+const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+/// Key the v0 layout used for a user, the `Debug` output of `UserId`
+fn legacy_key(user_id: &UserId) -> String {
+    format!("{:?}", user_id)
+}
+
+/// A shared context as stored by the original release: an owner, a signer who consented and
+/// signed, a reader, and one saved signature
+fn v0_fixture(hash: &str) -> Vec<u8> {
+    let mut participants = UnorderedSet::new();
+    let mut permissions = UnorderedMap::new();
+    for (user, permission) in [
+        (OWNER, PermissionLevel::Admin),
+        (SIGNER, PermissionLevel::Sign),
+        (READER, PermissionLevel::Read),
+    ] {
+        participants.insert(user).unwrap();
+        permissions.insert(legacy_key(&user), permission).unwrap();
+    }
+
+    let mut documents = UnorderedMap::new();
+    documents
+        .insert(
+            LEGACY_ID.to_string(),
+            DocumentInfoV0 {
+                id: LEGACY_ID.to_string(),
+                name: "contract.pdf".to_string(),
+                hash: hash.to_string(),
+                uploaded_by: OWNER,
+                uploaded_at: 1_700_000_000,
+                status: DocumentStatus::PartiallySigned,
+                pdf_blob_id: [7; 32],
+                size: 1024,
+                embeddings: None,
+                extracted_text: None,
+                chunks: None,
+            },
+        )
+        .unwrap();
+
+    let mut legacy_signatures = Vector::new();
+    legacy_signatures
+        .push(DocumentSignatureV0 {
+            signer: SIGNER,
+            signed_at: 1_700_000_100,
+        })
+        .unwrap();
+    let mut document_signatures = UnorderedMap::new();
+    document_signatures
+        .insert(LEGACY_ID.to_string(), legacy_signatures)
+        .unwrap();
+
+    let mut consents = UnorderedMap::new();
+    consents
+        .insert(format!("{}|{}", legacy_key(&SIGNER), LEGACY_ID), true)
+        .unwrap();
+
+    let mut signatures = UnorderedMap::new();
+    signatures
+        .insert(
+            "0".to_string(),
+            SignatureRecordV11 {
+                id: 0,
+                name: "Main".to_string(),
+                blob_id: [9; 32],
+                size: 64,
+                created_at: 1_699_999_000,
+            },
+        )
+        .unwrap();
+
+    to_vec(&MeroDocsStateV0 {
+        is_private: false,
+        owner: OWNER,
+        context_name: "Lease".to_string(),
+        signatures,
+        joined_contexts: UnorderedMap::new(),
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 1,
+        participants,
+        documents,
+        document_signatures,
+        permissions,
+        consents,
+    })
+    .unwrap()
+}
+
+#[test]
+fn v0_state_is_detected_by_layout() {
+    assert_eq!(detect_version(&v0_fixture(HASH)).unwrap(), 0);
+}
+
+#[test]
+fn v0_state_survives_migration() {
+    let state = upgrade(&v0_fixture(HASH)).expect("v0 state migrates");
+
+    assert_eq!(state.schema_version, SCHEMA_VERSION);
+    assert_eq!(state.owner, OWNER);
+    assert_eq!(state.context_name, "Lease");
+
+    assert_eq!(
+        state.permissions.get(&OWNER).unwrap(),
+        Some(PermissionLevel::Admin)
+    );
+    assert_eq!(
+        state.permissions.get(&SIGNER).unwrap(),
+        Some(PermissionLevel::Sign)
+    );
+    assert_eq!(
+        state.permissions.get(&READER).unwrap(),
+        Some(PermissionLevel::Read)
+    );
+
+    let documents: Vec<_> = state.documents.entries().unwrap().collect();
+    assert_eq!(documents.len(), 1);
+    let (document_id, document) = &documents[0];
+    assert_eq!(document.id, *document_id);
+    assert_eq!(document.name, "contract.pdf");
+    assert_eq!(document.hash, HASH.parse::<Sha256Digest>().unwrap());
+    assert_eq!(document.status, DocumentStatus::PartiallySigned);
+    assert_eq!(document.pdf_blob_id, [7; 32]);
+    assert!(document.required_signers.contains(&OWNER));
+    assert!(document.required_signers.contains(&SIGNER));
+    assert!(!document.required_signers.contains(&READER));
+
+    let signatures = state.document_signatures.get(document_id).unwrap().unwrap();
+    assert_eq!(signatures.len().unwrap(), 1);
+    let signature = signatures.get(0).unwrap().unwrap();
+    assert_eq!(signature.signer, SIGNER);
+    assert_eq!(signature.signed_at, 1_700_000_100);
+    assert_eq!(signature.document_hash, document.hash);
+
+    let revision = state
+        .document_revisions
+        .get(document_id)
+        .unwrap()
+        .unwrap()
+        .get(0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        revision.reason,
+        RevisionReason::Migrated {
+            legacy_id: LEGACY_ID.to_string(),
+            legacy_hash: HASH.to_string(),
+        }
+    );
+    assert_eq!(
+        state
+            .find_document_by_legacy_id(LEGACY_ID.to_string())
+            .unwrap(),
+        *document_id
+    );
+
+    let consent = state
+        .consents
+        .get(&ConsentKey::for_document(&SIGNER, document_id))
+        .unwrap()
+        .unwrap();
+    assert_eq!(consent.len().unwrap(), 1);
+    assert!(consent.get(0).unwrap().unwrap().given);
+
+    let saved = state.signatures.get("0").unwrap().unwrap();
+    assert_eq!(saved.name, "Main");
+    assert_eq!(saved.kind, SignatureKind::Signature);
+    assert!(saved.is_default);
+}
+
+#[test]
+fn v0_hash_that_is_not_a_digest_stays_unverified() {
+    let state = upgrade(&v0_fixture("uploaded-by-an-old-client")).expect("v0 state migrates");
+
+    let (document_id, document) = state.documents.entries().unwrap().next().unwrap();
+    assert_eq!(document.hash, Sha256Digest::UNVERIFIED);
+
+    let revision = state
+        .document_revisions
+        .get(&document_id)
+        .unwrap()
+        .unwrap()
+        .get(0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        revision.reason,
+        RevisionReason::Migrated {
+            legacy_id: LEGACY_ID.to_string(),
+            legacy_hash: "uploaded-by-an-old-client".to_string(),
+        }
+    );
+}
+
+#[test]
+fn v1_state_is_detected_by_layout() {
+    let document_id = DocumentId::new([5; 32]);
+    let mut permissions = UnorderedMap::new();
+    permissions.insert(SIGNER, PermissionLevel::Sign).unwrap();
+    let mut consents = UnorderedMap::new();
+    consents
+        .insert(ConsentKey::for_document(&SIGNER, &document_id), true)
+        .unwrap();
+
+    let bytes = to_vec(&MeroDocsStateV1 {
+        is_private: false,
+        owner: OWNER,
+        context_name: "Lease".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts: UnorderedMap::new(),
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions,
+        consents,
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 1);
+
+    let state = upgrade(&bytes).expect("v1 state migrates");
+    assert_eq!(state.schema_version, SCHEMA_VERSION);
+    assert_eq!(
+        state.permissions.get(&SIGNER).unwrap(),
+        Some(PermissionLevel::Sign)
+    );
+    let history = state
+        .consents
+        .get(&ConsentKey::for_document(&SIGNER, &document_id))
+        .unwrap()
+        .unwrap();
+    assert!(history.get(0).unwrap().unwrap().given);
+}
+
+#[test]
+fn v2_state_is_detected_by_its_header() {
+    let bytes = to_vec(&MeroDocsStateV2 {
+        schema_version: 2,
+        is_private: true,
+        owner: OWNER,
+        context_name: "Private".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts: UnorderedMap::new(),
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 3,
+        permissions: UnorderedMap::new(),
+        consents: UnorderedMap::new(),
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 2);
+
+    let state = upgrade(&bytes).expect("v2 state migrates");
+    assert_eq!(state.schema_version, SCHEMA_VERSION);
+    assert!(state.is_private);
+    assert_eq!(state.document_count, 3);
+    assert_eq!(state.audit_log.len().unwrap(), 0);
+}
+
+#[test]
+fn v3_audit_entries_are_chained_in_order() {
+    let mut audit_log = Vector::new();
+    for (sequence, action) in [
+        (0, AuditAction::DocumentUploaded),
+        (1, AuditAction::SignatureApplied),
+    ] {
+        audit_log
+            .push(AuditEntryV3 {
+                sequence,
+                actor: OWNER,
+                action,
+                timestamp: 1_700_000_000 + sequence,
+                document_id: Some(DocumentId::new([5; 32])),
+                subject: None,
+                document_hash: None,
+                details: None,
+            })
+            .unwrap();
+    }
+
+    let bytes = to_vec(&MeroDocsStateV3 {
+        schema_version: 3,
+        is_private: false,
+        owner: OWNER,
+        context_name: "Lease".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts: UnorderedMap::new(),
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions: UnorderedMap::new(),
+        consents: UnorderedMap::new(),
+        audit_log,
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 3);
+
+    let state = upgrade(&bytes).expect("v3 state migrates");
+    let entries: Vec<_> = state.audit_log.iter().unwrap().collect();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].previous_hash, None);
+    assert_eq!(entries[1].previous_hash, Some(entries[0].entry_hash));
+    assert_eq!(entries[1].action, AuditAction::SignatureApplied);
+    for entry in &entries {
+        assert_eq!(entry.compute_hash(), entry.entry_hash);
+    }
+}
+
+#[test]
+fn v4_context_starts_active_without_deadlines() {
+    let mut entry = AuditEntryV11 {
+        sequence: 0,
+        actor: OWNER,
+        action: AuditAction::DocumentUploaded,
+        timestamp: 1_700_000_000,
+        document_id: None,
+        subject: None,
+        document_hash: None,
+        details: Some("contract.pdf".to_string()),
+        previous_hash: None,
+        entry_hash: Sha256Digest::new([0; 32]),
+    };
+    entry.entry_hash = entry.compute_hash();
+    let entry_hash = entry.entry_hash;
+    let mut audit_log = Vector::new();
+    audit_log.push(entry).unwrap();
+
+    let bytes = to_vec(&MeroDocsStateV4 {
+        schema_version: 4,
+        is_private: false,
+        owner: OWNER,
+        context_name: "Lease".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts: UnorderedMap::new(),
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions: UnorderedMap::new(),
+        consents: UnorderedMap::new(),
+        audit_log,
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 4);
+
+    let state = upgrade(&bytes).expect("v4 state migrates");
+    assert_eq!(state.context_status, ContextStatus::Active);
+    assert_eq!(state.context_deadline, None);
+    assert_eq!(state.document_deadlines.entries().unwrap().count(), 0);
+    let migrated = state.audit_log.get(0).unwrap().unwrap();
+    assert_eq!(migrated.entry_hash, entry_hash);
+    assert_eq!(migrated.compute_hash(), entry_hash);
+}
+
+#[test]
+fn v5_consents_become_single_record_histories() {
+    let document_id = DocumentId::new([5; 32]);
+    let mut consents = UnorderedMap::new();
+    consents
+        .insert(ConsentKey::for_document(&SIGNER, &document_id), false)
+        .unwrap();
+
+    let bytes = to_vec(&MeroDocsStateV5 {
+        schema_version: 5,
+        is_private: false,
+        owner: OWNER,
+        context_name: "Lease".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts: UnorderedMap::new(),
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions: UnorderedMap::new(),
+        consents,
+        audit_log: Vector::new(),
+        context_status: ContextStatus::Expired,
+        context_deadline: Some(1_700_000_000),
+        document_deadlines: UnorderedMap::new(),
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 5);
+
+    let state = upgrade(&bytes).expect("v5 state migrates");
+    assert_eq!(state.schema_version, SCHEMA_VERSION);
+    assert_eq!(state.context_status, ContextStatus::Expired);
+    assert_eq!(state.context_deadline, Some(1_700_000_000));
+
+    let history = state
+        .consents
+        .get(&ConsentKey::for_document(&SIGNER, &document_id))
+        .unwrap()
+        .unwrap();
+    assert_eq!(history.len().unwrap(), 1);
+    let record = history.get(0).unwrap().unwrap();
+    assert!(!record.given);
+    assert_eq!(record.disclosure_version, None);
+    assert_eq!(record.disclosure_hash, None);
+    assert_eq!(record.recorded_at, 0);
+}
+
+#[test]
+fn v6_joined_contexts_drop_the_unassigned_role() {
+    let mut joined_contexts = UnorderedMap::new();
+    joined_contexts
+        .insert(
+            "ctx-1".to_string(),
+            ContextMetadataV6 {
+                context_id: "ctx-1".to_string(),
+                context_name: "Lease".to_string(),
+                role: ParticipantRoleV6::Signer,
+                joined_at: 1_700_000_000,
+                private_identity: OWNER,
+                shared_identity: SIGNER,
+            },
+        )
+        .unwrap();
+
+    let bytes = to_vec(&MeroDocsStateV6 {
+        schema_version: 6,
+        is_private: true,
+        owner: OWNER,
+        context_name: "Private".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts,
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions: UnorderedMap::new(),
+        consents: UnorderedMap::new(),
+        audit_log: Vector::new(),
+        context_status: ContextStatus::Active,
+        context_deadline: None,
+        document_deadlines: UnorderedMap::new(),
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 6);
+
+    let state = upgrade(&bytes).expect("v6 state migrates");
+    let metadata = state.joined_contexts.get("ctx-1").unwrap().unwrap();
+    assert_eq!(metadata.context_name, "Lease");
+    assert_eq!(metadata.role, None);
+    assert_eq!(metadata.shared_identity, SIGNER);
+    assert_eq!(metadata.membership, MembershipStatus::Active);
+}
+
+#[test]
+fn v7_context_starts_without_invitations() {
+    let mut permissions = UnorderedMap::new();
+    permissions.insert(SIGNER, PermissionLevel::Sign).unwrap();
+
+    let bytes = to_vec(&MeroDocsStateV7 {
+        schema_version: 7,
+        is_private: false,
+        owner: OWNER,
+        context_name: "Lease".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts: UnorderedMap::new(),
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions,
+        consents: UnorderedMap::new(),
+        audit_log: Vector::new(),
+        context_status: ContextStatus::Active,
+        context_deadline: None,
+        document_deadlines: UnorderedMap::new(),
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 7);
+
+    let state = upgrade(&bytes).expect("v7 state migrates");
+    assert_eq!(state.pending_invitations.entries().unwrap().count(), 0);
+    assert_eq!(
+        state.permissions.get(&SIGNER).unwrap(),
+        Some(PermissionLevel::Sign)
+    );
+}
+
+#[test]
+fn v8_joined_contexts_become_active_memberships() {
+    let mut joined_contexts = UnorderedMap::new();
+    joined_contexts
+        .insert(
+            "ctx-1".to_string(),
+            ContextMetadataV8 {
+                context_id: "ctx-1".to_string(),
+                context_name: "Lease".to_string(),
+                role: Some(PermissionLevel::Sign),
+                joined_at: 1_700_000_000,
+                private_identity: OWNER,
+                shared_identity: SIGNER,
+            },
+        )
+        .unwrap();
+
+    let bytes = to_vec(&MeroDocsStateV8 {
+        schema_version: 8,
+        is_private: true,
+        owner: OWNER,
+        context_name: "Private".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts,
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions: UnorderedMap::new(),
+        consents: UnorderedMap::new(),
+        audit_log: Vector::new(),
+        context_status: ContextStatus::Active,
+        context_deadline: None,
+        document_deadlines: UnorderedMap::new(),
+        pending_invitations: UnorderedMap::new(),
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 8);
+
+    let state = upgrade(&bytes).expect("v8 state migrates");
+    let metadata = state.joined_contexts.get("ctx-1").unwrap().unwrap();
+    assert_eq!(metadata.role, Some(PermissionLevel::Sign));
+    assert_eq!(metadata.membership, MembershipStatus::Active);
+    assert_eq!(metadata.document_count, None);
+    assert_eq!(metadata.synced_at, None);
+}
+
+/// Joined-context metadata for `context_id` as stored from v9 on
+fn joined_context_v11(context_id: &str, membership: MembershipStatus) -> ContextMetadataV11 {
+    ContextMetadataV11 {
+        context_id: context_id.to_string(),
+        context_name: "Lease".to_string(),
+        role: Some(PermissionLevel::Sign),
+        joined_at: 1_700_000_000,
+        private_identity: OWNER,
+        shared_identity: SIGNER,
+        membership,
+        document_count: Some(1),
+        synced_at: Some(1_700_000_500),
+    }
+}
+
+#[test]
+fn v9_mappings_of_left_contexts_are_archived() {
+    let mut joined_contexts = UnorderedMap::new();
+    let mut identity_mappings = UnorderedMap::new();
+    for (context_id, membership, shared_identity) in [
+        ("ctx-joined", MembershipStatus::Active, SIGNER),
+        ("ctx-removed", MembershipStatus::Removed, READER),
+    ] {
+        joined_contexts
+            .insert(
+                context_id.to_string(),
+                joined_context_v11(context_id, membership),
+            )
+            .unwrap();
+        identity_mappings
+            .insert(
+                context_id.to_string(),
+                IdentityMappingV9 {
+                    private_identity: OWNER,
+                    shared_identity,
+                    context_id: context_id.to_string(),
+                    created_at: 1_700_000_000,
+                },
+            )
+            .unwrap();
+    }
+
+    let bytes = to_vec(&MeroDocsStateV9 {
+        schema_version: 9,
+        is_private: true,
+        owner: OWNER,
+        context_name: "Private".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts,
+        identity_mappings,
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions: UnorderedMap::new(),
+        consents: UnorderedMap::new(),
+        audit_log: Vector::new(),
+        context_status: ContextStatus::Active,
+        context_deadline: None,
+        document_deadlines: UnorderedMap::new(),
+        pending_invitations: UnorderedMap::new(),
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 9);
+
+    let state = upgrade(&bytes).expect("v9 state migrates");
+    let active = state.identity_mappings.get("ctx-joined").unwrap().unwrap();
+    assert_eq!(active.left_at, None);
+    assert!(state
+        .identity_mappings
+        .get("ctx-removed")
+        .unwrap()
+        .is_none());
+
+    let archived = state
+        .archived_identity_mappings
+        .get("ctx-removed")
+        .unwrap()
+        .unwrap();
+    assert_eq!(archived.len().unwrap(), 1);
+    let archived = archived.get(0).unwrap().unwrap();
+    assert_eq!(archived.shared_identity, READER);
+    assert_eq!(archived.left_at, Some(0));
+
+    assert_eq!(
+        state.shared_identity_index.get(&SIGNER).unwrap(),
+        Some("ctx-joined".to_string())
+    );
+    assert_eq!(state.shared_identity_index.get(&READER).unwrap(), None);
+}
+
+#[test]
+fn v10_active_mappings_are_indexed_by_shared_identity() {
+    let mut identity_mappings = UnorderedMap::new();
+    identity_mappings
+        .insert(
+            "ctx-1".to_string(),
+            IdentityMappingV11 {
+                private_identity: OWNER,
+                shared_identity: SIGNER,
+                context_id: "ctx-1".to_string(),
+                created_at: 1_700_000_000,
+                left_at: None,
+            },
+        )
+        .unwrap();
+
+    let bytes = to_vec(&MeroDocsStateV10 {
+        schema_version: 10,
+        is_private: true,
+        owner: OWNER,
+        context_name: "Private".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts: UnorderedMap::new(),
+        identity_mappings,
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions: UnorderedMap::new(),
+        consents: UnorderedMap::new(),
+        audit_log: Vector::new(),
+        context_status: ContextStatus::Active,
+        context_deadline: None,
+        document_deadlines: UnorderedMap::new(),
+        pending_invitations: UnorderedMap::new(),
+        archived_identity_mappings: UnorderedMap::new(),
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 10);
+
+    let state = upgrade(&bytes).expect("v10 state migrates");
+    assert_eq!(
+        state.shared_identity_index.get(&SIGNER).unwrap(),
+        Some("ctx-1".to_string())
+    );
+}
+
+#[test]
+fn v11_values_are_rewritten_into_current_types() {
+    let document_id = DocumentId::new([5; 32]);
+    let document_hash: Sha256Digest = HASH.parse().unwrap();
+
+    let mut signatures = UnorderedMap::new();
+    for id in [3, 1] {
+        signatures
+            .insert(
+                id.to_string(),
+                SignatureRecordV11 {
+                    id,
+                    name: format!("Signature {}", id),
+                    blob_id: [9; 32],
+                    size: 64,
+                    created_at: 1_699_999_000 + id,
+                },
+            )
+            .unwrap();
+    }
+
+    let mut joined_contexts = UnorderedMap::new();
+    joined_contexts
+        .insert(
+            "ctx-1".to_string(),
+            joined_context_v11("ctx-1", MembershipStatus::Active),
+        )
+        .unwrap();
+
+    let mut documents = UnorderedMap::new();
+    documents
+        .insert(
+            document_id,
+            DocumentInfoV11 {
+                id: document_id,
+                name: "contract.pdf".to_string(),
+                hash: document_hash,
+                uploaded_by: OWNER,
+                uploaded_at: 1_700_000_000,
+                status: DocumentStatus::PartiallySigned,
+                pdf_blob_id: [7; 32],
+                size: 1024,
+                embeddings: None,
+                extracted_text: Some("Lease agreement".to_string()),
+                chunks: Some(vec![DocumentChunkV11 {
+                    text: "Lease agreement".to_string(),
+                    embedding: vec![0.5],
+                    start_position: 0,
+                    end_position: 15,
+                }]),
+                required_signers: vec![OWNER, SIGNER],
+                signing_mode: SigningMode::Sequential,
+            },
+        )
+        .unwrap();
+
+    let mut signed = Vector::new();
+    signed
+        .push(DocumentSignatureV11 {
+            signer: SIGNER,
+            signed_by: OWNER,
+            signed_at: 1_700_000_100,
+            document_hash,
+            signature: Some(Ed25519Signature::new([4; 64])),
+        })
+        .unwrap();
+    let mut document_signatures = UnorderedMap::new();
+    document_signatures.insert(document_id, signed).unwrap();
+
+    let mut revisions = Vector::new();
+    revisions
+        .push(DocumentRevisionV11 {
+            revision: 0,
+            blob_id: [7; 32],
+            hash: document_hash,
+            size: 1024,
+            author: OWNER,
+            created_at: 1_700_000_000,
+            reason: RevisionReasonV11::Upload,
+        })
+        .unwrap();
+    let mut document_revisions = UnorderedMap::new();
+    document_revisions.insert(document_id, revisions).unwrap();
+
+    let mut history = Vector::new();
+    history
+        .push(ConsentRecordV11 {
+            given: true,
+            disclosure_version: Some("2024-01".to_string()),
+            disclosure_hash: None,
+            recorded_at: 1_700_000_050,
+        })
+        .unwrap();
+    let mut consents = UnorderedMap::new();
+    consents
+        .insert(ConsentKey::for_document(&SIGNER, &document_id), history)
+        .unwrap();
+
+    let mut pending_invitations = UnorderedMap::new();
+    pending_invitations
+        .insert(
+            READER,
+            InvitationV11 {
+                user_id: READER,
+                role: PermissionLevel::Read,
+                invited_by: OWNER,
+                invited_at: 1_700_000_200,
+                expires_at: Some(1_700_086_600),
+            },
+        )
+        .unwrap();
+
+    let mut archived = Vector::new();
+    archived
+        .push(IdentityMappingV11 {
+            private_identity: OWNER,
+            shared_identity: READER,
+            context_id: "ctx-0".to_string(),
+            created_at: 1_690_000_000,
+            left_at: Some(1_695_000_000),
+        })
+        .unwrap();
+    let mut archived_identity_mappings = UnorderedMap::new();
+    archived_identity_mappings
+        .insert("ctx-0".to_string(), archived)
+        .unwrap();
+
+    let bytes = to_vec(&MeroDocsStateV11 {
+        schema_version: 11,
+        is_private: false,
+        owner: OWNER,
+        context_name: "Lease".to_string(),
+        signatures,
+        joined_contexts,
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 4,
+        participants: UnorderedSet::new(),
+        documents,
+        document_signatures,
+        document_revisions,
+        document_count: 1,
+        permissions: UnorderedMap::new(),
+        consents,
+        audit_log: Vector::new(),
+        context_status: ContextStatus::Active,
+        context_deadline: None,
+        document_deadlines: UnorderedMap::new(),
+        pending_invitations,
+        archived_identity_mappings,
+        shared_identity_index: UnorderedMap::new(),
+    })
+    .unwrap();
+    assert_eq!(detect_version(&bytes).unwrap(), 11);
+
+    let state = upgrade(&bytes).expect("v11 state migrates");
+    assert_eq!(state.schema_version, SCHEMA_VERSION);
+
+    let oldest = state.signatures.get("1").unwrap().unwrap();
+    assert_eq!(oldest.kind, SignatureKind::Signature);
+    assert_eq!(oldest.mime_type, "image/png");
+    assert!(oldest.is_default);
+    assert!(!state.signatures.get("3").unwrap().unwrap().is_default);
+
+    let metadata = state.joined_contexts.get("ctx-1").unwrap().unwrap();
+    assert_eq!(metadata.synced_at, Some(1_700_000_500));
+
+    let document = state.documents.get(&document_id).unwrap().unwrap();
+    assert_eq!(document.hash, document_hash);
+    assert_eq!(document.required_signers, vec![OWNER, SIGNER]);
+    assert_eq!(document.signing_mode, SigningMode::Sequential);
+    assert_eq!(document.chunks.unwrap()[0].end_position, 15);
+
+    let signature = state
+        .document_signatures
+        .get(&document_id)
+        .unwrap()
+        .unwrap()
+        .get(0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(signature.signed_by, OWNER);
+    assert_eq!(signature.signature, Some(Ed25519Signature::new([4; 64])));
+
+    let revision = state
+        .document_revisions
+        .get(&document_id)
+        .unwrap()
+        .unwrap()
+        .get(0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(revision.reason, RevisionReason::Upload);
+
+    let consent = state
+        .consents
+        .get(&ConsentKey::for_document(&SIGNER, &document_id))
+        .unwrap()
+        .unwrap()
+        .get(0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(consent.disclosure_version, Some("2024-01".to_string()));
+
+    let invitation = state.pending_invitations.get(&READER).unwrap().unwrap();
+    assert_eq!(invitation.expires_at, Some(1_700_086_600));
+
+    let archived = state
+        .archived_identity_mappings
+        .get("ctx-0")
+        .unwrap()
+        .unwrap()
+        .get(0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(archived.left_at, Some(1_695_000_000));
+}
+
+#[test]
+fn unknown_schema_version_is_rejected() {
+    let mut bytes = (SCHEMA_VERSION + 1).to_le_bytes().to_vec();
+    bytes.extend_from_slice(&[0; 64]);
+
+    assert!(matches!(
+        upgrade(&bytes),
+        Err(MeroDocsError::StorageError(_))
+    ));
+}
@@ -0,0 +1,231 @@
+//! Schema version 0: the original layout, keyed by `Debug`-formatted strings
+
+use calimero_sdk::borsh::BorshDeserialize;
+#[cfg(test)]
+use calimero_sdk::borsh::BorshSerialize;
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use crate::migrations::v1::MeroDocsStateV1;
+use crate::migrations::v11::{
+    DocumentChunkV11, DocumentInfoV11, DocumentRevisionV11, DocumentSignatureV11,
+    RevisionReasonV11, SignatureRecordV11,
+};
+use crate::migrations::v6::ContextMetadataV6;
+use crate::migrations::v9::IdentityMappingV9;
+use crate::types::hash::Sha256Digest;
+use crate::types::id::{ConsentKey, UserId};
+use crate::{derive_document_id, DocumentStatus, MeroDocsError, PermissionLevel, SigningMode};
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroDocsStateV0 {
+    pub is_private: bool,
+    pub owner: UserId,
+    pub context_name: String,
+    pub signatures: UnorderedMap<String, SignatureRecordV11>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadataV6>,
+    pub identity_mappings: UnorderedMap<String, IdentityMappingV9>,
+    pub signature_count: u64,
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<String, DocumentInfoV0>,
+    pub document_signatures: UnorderedMap<String, Vector<DocumentSignatureV0>>,
+    pub permissions: UnorderedMap<String, PermissionLevel>, // Debug-formatted user ID -> permission
+    pub consents: UnorderedMap<String, bool>,               // "user_id|document_id" -> consent
+}
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct DocumentInfoV0 {
+    pub id: String,
+    pub name: String,
+    pub hash: String,
+    pub uploaded_by: UserId,
+    pub uploaded_at: u64,
+    pub status: DocumentStatus,
+    pub pdf_blob_id: [u8; 32],
+    pub size: u64,
+    pub embeddings: Option<Vec<f32>>,
+    pub extracted_text: Option<String>,
+    pub chunks: Option<Vec<DocumentChunkV11>>,
+}
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct DocumentSignatureV0 {
+    pub signer: UserId,
+    pub signed_at: u64,
+}
+
+/// Key the legacy layout used for a user; relies on the derived `Debug` output of `UserId`
+fn legacy_user_key(user_id: &UserId) -> String {
+    format!("{:?}", user_id)
+}
+
+/// Legacy hashes were caller-supplied. Blobs may not be available locally during an upgrade, so
+/// one that is not valid hex is left `UNVERIFIED` until `verify_migrated_document` digests it.
+fn legacy_digest(hash: &str) -> Sha256Digest {
+    hash.parse().unwrap_or(Sha256Digest::UNVERIFIED)
+}
+
+impl MeroDocsStateV0 {
+    /// Re-key permissions, documents and consents by typed IDs
+    pub fn migrate(mut self) -> Result<MeroDocsStateV1, MeroDocsError> {
+        let mut state = MeroDocsStateV1 {
+            is_private: self.is_private,
+            owner: self.owner,
+            context_name: self.context_name,
+
+            signatures: self.signatures,
+            joined_contexts: self.joined_contexts,
+            identity_mappings: self.identity_mappings,
+            signature_count: self.signature_count,
+            participants: self.participants,
+            documents: UnorderedMap::new(),
+            document_signatures: UnorderedMap::new(),
+            document_revisions: UnorderedMap::new(),
+            document_count: 0,
+            permissions: UnorderedMap::new(),
+            consents: UnorderedMap::new(),
+        };
+
+        let participants: Vec<UserId> = state
+            .participants
+            .iter()
+            .map_err(|e| MeroDocsError::storage("Failed to read participants", e))?
+            .collect();
+
+        let mut signers = Vec::new();
+        for participant in &participants {
+            let permission = self
+                .permissions
+                .get(&legacy_user_key(participant))
+                .map_err(|e| MeroDocsError::storage("Failed to read legacy permission", e))?;
+            if let Some(permission) = permission {
+                if permission != PermissionLevel::Read {
+                    signers.push(*participant);
+                }
+                state
+                    .permissions
+                    .insert(*participant, permission)
+                    .map_err(|e| MeroDocsError::storage("Failed to migrate permission", e))?;
+            }
+        }
+
+        let legacy_documents: Vec<(String, DocumentInfoV0)> = self
+            .documents
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to read legacy documents", e))?
+            .collect();
+
+        for (legacy_id, legacy) in legacy_documents {
+            let hash = legacy_digest(&legacy.hash);
+            let document_id = derive_document_id(&legacy.uploaded_by, &hash, state.document_count);
+            state.document_count += 1;
+
+            let mut signatures = Vector::new();
+            let legacy_signatures = self
+                .document_signatures
+                .get(&legacy_id)
+                .map_err(|e| MeroDocsError::storage("Failed to read legacy signatures", e))?;
+            if let Some(mut legacy_signatures) = legacy_signatures {
+                for sig in legacy_signatures
+                    .iter()
+                    .map_err(|e| MeroDocsError::storage("Failed to read legacy signatures", e))?
+                {
+                    signatures
+                        .push(DocumentSignatureV11 {
+                            signer: sig.signer,
+                            signed_by: sig.signer,
+                            signed_at: sig.signed_at,
+                            document_hash: hash,
+                            signature: None,
+                        })
+                        .map_err(|e| MeroDocsError::storage("Failed to migrate signature", e))?;
+                }
+                legacy_signatures
+                    .clear()
+                    .map_err(|e| MeroDocsError::storage("Failed to clear legacy signatures", e))?;
+            }
+
+            for participant in &participants {
+                let legacy_key = format!("{}|{}", legacy_user_key(participant), legacy_id);
+                let consented = self
+                    .consents
+                    .get(&legacy_key)
+                    .map_err(|e| MeroDocsError::storage("Failed to read legacy consent", e))?;
+                if let Some(consented) = consented {
+                    state
+                        .consents
+                        .insert(
+                            ConsentKey::for_document(participant, &document_id),
+                            consented,
+                        )
+                        .map_err(|e| MeroDocsError::storage("Failed to migrate consent", e))?;
+                }
+            }
+
+            let mut revisions = Vector::new();
+            revisions
+                .push(DocumentRevisionV11 {
+                    revision: 0,
+                    blob_id: legacy.pdf_blob_id,
+                    hash,
+                    size: legacy.size,
+                    author: legacy.uploaded_by,
+                    created_at: legacy.uploaded_at,
+                    reason: RevisionReasonV11::Migrated {
+                        legacy_id,
+                        legacy_hash: legacy.hash,
+                    },
+                })
+                .map_err(|e| MeroDocsError::storage("Failed to migrate document revision", e))?;
+
+            let document = DocumentInfoV11 {
+                id: document_id,
+                name: legacy.name,
+                hash,
+                uploaded_by: legacy.uploaded_by,
+                uploaded_at: legacy.uploaded_at,
+                status: legacy.status,
+                pdf_blob_id: legacy.pdf_blob_id,
+                size: legacy.size,
+                embeddings: legacy.embeddings,
+                extracted_text: legacy.extracted_text,
+                chunks: legacy.chunks,
+                required_signers: signers.clone(),
+                signing_mode: SigningMode::Parallel,
+            };
+
+            state
+                .documents
+                .insert(document_id, document)
+                .map_err(|e| MeroDocsError::storage("Failed to migrate document", e))?;
+            state
+                .document_signatures
+                .insert(document_id, signatures)
+                .map_err(|e| MeroDocsError::storage("Failed to migrate document signatures", e))?;
+            state
+                .document_revisions
+                .insert(document_id, revisions)
+                .map_err(|e| MeroDocsError::storage("Failed to migrate document revisions", e))?;
+        }
+
+        self.documents
+            .clear()
+            .map_err(|e| MeroDocsError::storage("Failed to clear legacy documents", e))?;
+        self.document_signatures
+            .clear()
+            .map_err(|e| MeroDocsError::storage("Failed to clear legacy signatures", e))?;
+        self.permissions
+            .clear()
+            .map_err(|e| MeroDocsError::storage("Failed to clear legacy permissions", e))?;
+        self.consents
+            .clear()
+            .map_err(|e| MeroDocsError::storage("Failed to clear legacy consents", e))?;
+
+        Ok(state)
+    }
+}
@@ -0,0 +1,59 @@
+//! Schema version 1: typed storage keys, before the schema version was recorded in state
+
+use calimero_sdk::borsh::BorshDeserialize;
+#[cfg(test)]
+use calimero_sdk::borsh::BorshSerialize;
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use crate::migrations::v11::{
+    DocumentInfoV11, DocumentRevisionV11, DocumentSignatureV11, SignatureRecordV11,
+};
+use crate::migrations::v2::MeroDocsStateV2;
+use crate::migrations::v6::ContextMetadataV6;
+use crate::migrations::v9::IdentityMappingV9;
+use crate::types::id::{ConsentKey, DocumentId, UserId};
+use crate::{MeroDocsError, PermissionLevel};
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroDocsStateV1 {
+    pub is_private: bool,
+    pub owner: UserId,
+    pub context_name: String,
+    pub signatures: UnorderedMap<String, SignatureRecordV11>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadataV6>,
+    pub identity_mappings: UnorderedMap<String, IdentityMappingV9>,
+    pub signature_count: u64,
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<DocumentId, DocumentInfoV11>,
+    pub document_signatures: UnorderedMap<DocumentId, Vector<DocumentSignatureV11>>,
+    pub document_revisions: UnorderedMap<DocumentId, Vector<DocumentRevisionV11>>,
+    pub document_count: u64,
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub consents: UnorderedMap<ConsentKey, bool>,
+}
+
+impl MeroDocsStateV1 {
+    /// Record the schema version in state
+    pub fn migrate(self) -> Result<MeroDocsStateV2, MeroDocsError> {
+        Ok(MeroDocsStateV2 {
+            schema_version: 2,
+            is_private: self.is_private,
+            owner: self.owner,
+            context_name: self.context_name,
+
+            signatures: self.signatures,
+            joined_contexts: self.joined_contexts,
+            identity_mappings: self.identity_mappings,
+            signature_count: self.signature_count,
+            participants: self.participants,
+            documents: self.documents,
+            document_signatures: self.document_signatures,
+            document_revisions: self.document_revisions,
+            document_count: self.document_count,
+            permissions: self.permissions,
+            consents: self.consents,
+        })
+    }
+}
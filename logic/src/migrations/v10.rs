@@ -0,0 +1,82 @@
+//! Schema version 10: identity mapping history, before the shared identity index
+
+use calimero_sdk::borsh::BorshDeserialize;
+#[cfg(test)]
+use calimero_sdk::borsh::BorshSerialize;
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use super::v11::{
+    AuditEntryV11, ConsentRecordV11, ContextMetadataV11, DocumentInfoV11, DocumentRevisionV11,
+    DocumentSignatureV11, IdentityMappingV11, InvitationV11, MeroDocsStateV11, SignatureRecordV11,
+};
+use crate::types::id::{ConsentKey, DocumentId, UserId};
+use crate::{ContextStatus, MeroDocsError, PermissionLevel};
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroDocsStateV10 {
+    pub schema_version: u32,
+    pub is_private: bool,
+    pub owner: UserId,
+    pub context_name: String,
+    pub signatures: UnorderedMap<String, SignatureRecordV11>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadataV11>,
+    pub identity_mappings: UnorderedMap<String, IdentityMappingV11>,
+    pub signature_count: u64,
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<DocumentId, DocumentInfoV11>,
+    pub document_signatures: UnorderedMap<DocumentId, Vector<DocumentSignatureV11>>,
+    pub document_revisions: UnorderedMap<DocumentId, Vector<DocumentRevisionV11>>,
+    pub document_count: u64,
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub consents: UnorderedMap<ConsentKey, Vector<ConsentRecordV11>>,
+    pub audit_log: Vector<AuditEntryV11>,
+    pub context_status: ContextStatus,
+    pub context_deadline: Option<u64>,
+    pub document_deadlines: UnorderedMap<DocumentId, u64>,
+    pub pending_invitations: UnorderedMap<UserId, InvitationV11>,
+    pub archived_identity_mappings: UnorderedMap<String, Vector<IdentityMappingV11>>,
+}
+
+impl MeroDocsStateV10 {
+    /// Index the shared identity of every active identity mapping
+    pub fn migrate(self) -> Result<MeroDocsStateV11, MeroDocsError> {
+        let mut shared_identity_index = UnorderedMap::new();
+        let mappings = self
+            .identity_mappings
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to read identity mappings", e))?;
+        for (context_id, mapping) in mappings {
+            shared_identity_index
+                .insert(mapping.shared_identity, context_id)
+                .map_err(|e| MeroDocsError::storage("Failed to index shared identity", e))?;
+        }
+
+        Ok(MeroDocsStateV11 {
+            schema_version: 11,
+            is_private: self.is_private,
+            owner: self.owner,
+            context_name: self.context_name,
+
+            signatures: self.signatures,
+            joined_contexts: self.joined_contexts,
+            identity_mappings: self.identity_mappings,
+            signature_count: self.signature_count,
+            participants: self.participants,
+            documents: self.documents,
+            document_signatures: self.document_signatures,
+            document_revisions: self.document_revisions,
+            document_count: self.document_count,
+            permissions: self.permissions,
+            consents: self.consents,
+            audit_log: self.audit_log,
+            context_status: self.context_status,
+            context_deadline: self.context_deadline,
+            document_deadlines: self.document_deadlines,
+            pending_invitations: self.pending_invitations,
+            archived_identity_mappings: self.archived_identity_mappings,
+            shared_identity_index,
+        })
+    }
+}
@@ -0,0 +1,471 @@
+//! Schema version 11: shared identity index, before signature kinds and defaults.
+//!
+//! Also holds the layouts every earlier version shares with this one, so a later change to the
+//! live types cannot silently alter how older states are read.
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+use sha2::{Digest, Sha256};
+
+use crate::types::hash::Sha256Digest;
+use crate::types::id::{ConsentKey, DocumentId, Ed25519Signature, UserId};
+use crate::{
+    AuditAction, AuditEntry, ConsentRecord, ContextMetadata, ContextStatus, DocumentChunk,
+    DocumentInfo, DocumentRevision, DocumentSignature, DocumentStatus, IdentityMapping, Invitation,
+    MembershipStatus, MeroDocsError, MeroDocsState, PermissionLevel, RevisionReason, SignatureKind,
+    SignatureRecord, SigningMode,
+};
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct SignatureRecordV11 {
+    pub id: u64,
+    pub name: String,
+    pub blob_id: [u8; 32],
+    pub size: u64,
+    pub created_at: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct DocumentChunkV11 {
+    pub text: String,
+    pub embedding: Vec<f32>,
+    pub start_position: usize,
+    pub end_position: usize,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct DocumentInfoV11 {
+    pub id: DocumentId,
+    pub name: String,
+    pub hash: Sha256Digest,
+    pub uploaded_by: UserId,
+    pub uploaded_at: u64,
+    pub status: DocumentStatus,
+    pub pdf_blob_id: [u8; 32],
+    pub size: u64,
+    pub embeddings: Option<Vec<f32>>,
+    pub extracted_text: Option<String>,
+    pub chunks: Option<Vec<DocumentChunkV11>>,
+    pub required_signers: Vec<UserId>,
+    pub signing_mode: SigningMode,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct DocumentSignatureV11 {
+    pub signer: UserId,
+    pub signed_by: UserId,
+    pub signed_at: u64,
+    pub document_hash: Sha256Digest,
+    pub signature: Option<Ed25519Signature>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub enum RevisionReasonV11 {
+    Upload,
+    Signature,
+    Amendment,
+    Migrated {
+        legacy_id: String,
+        legacy_hash: String,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct DocumentRevisionV11 {
+    pub revision: u64,
+    pub blob_id: [u8; 32],
+    pub hash: Sha256Digest,
+    pub size: u64,
+    pub author: UserId,
+    pub created_at: u64,
+    pub reason: RevisionReasonV11,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct AuditEntryV11 {
+    pub sequence: u64,
+    pub actor: UserId,
+    pub action: AuditAction,
+    pub timestamp: u64,
+    pub document_id: Option<DocumentId>,
+    pub subject: Option<UserId>,
+    pub document_hash: Option<Sha256Digest>,
+    pub details: Option<String>,
+    pub previous_hash: Option<Sha256Digest>,
+    pub entry_hash: Sha256Digest,
+}
+
+impl AuditEntryV11 {
+    /// SHA-256 over the Borsh encoding of every field except `entry_hash`
+    pub fn compute_hash(&self) -> Sha256Digest {
+        let encoded = calimero_sdk::borsh::to_vec(&(
+            &self.previous_hash,
+            self.sequence,
+            &self.actor,
+            &self.action,
+            self.timestamp,
+            &self.document_id,
+            &self.subject,
+            &self.document_hash,
+            &self.details,
+        ))
+        .expect("serializing an audit entry cannot fail");
+
+        Sha256Digest::new(Sha256::digest(encoded).into())
+    }
+
+    fn migrate(self) -> AuditEntry {
+        AuditEntry {
+            sequence: self.sequence,
+            actor: self.actor,
+            action: self.action,
+            timestamp: self.timestamp,
+            document_id: self.document_id,
+            subject: self.subject,
+            document_hash: self.document_hash,
+            details: self.details,
+            previous_hash: self.previous_hash,
+            entry_hash: self.entry_hash,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct ConsentRecordV11 {
+    pub given: bool,
+    pub disclosure_version: Option<String>,
+    pub disclosure_hash: Option<Sha256Digest>,
+    pub recorded_at: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct InvitationV11 {
+    pub user_id: UserId,
+    pub role: PermissionLevel,
+    pub invited_by: UserId,
+    pub invited_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct ContextMetadataV11 {
+    pub context_id: String,
+    pub context_name: String,
+    pub role: Option<PermissionLevel>,
+    pub joined_at: u64,
+    pub private_identity: UserId,
+    pub shared_identity: UserId,
+    pub membership: MembershipStatus,
+    pub document_count: Option<u64>,
+    pub synced_at: Option<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct IdentityMappingV11 {
+    pub private_identity: UserId,
+    pub shared_identity: UserId,
+    pub context_id: String,
+    pub created_at: u64,
+    pub left_at: Option<u64>,
+}
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroDocsStateV11 {
+    pub schema_version: u32,
+    pub is_private: bool,
+    pub owner: UserId,
+    pub context_name: String,
+    pub signatures: UnorderedMap<String, SignatureRecordV11>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadataV11>,
+    pub identity_mappings: UnorderedMap<String, IdentityMappingV11>,
+    pub signature_count: u64,
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<DocumentId, DocumentInfoV11>,
+    pub document_signatures: UnorderedMap<DocumentId, Vector<DocumentSignatureV11>>,
+    pub document_revisions: UnorderedMap<DocumentId, Vector<DocumentRevisionV11>>,
+    pub document_count: u64,
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub consents: UnorderedMap<ConsentKey, Vector<ConsentRecordV11>>,
+    pub audit_log: Vector<AuditEntryV11>,
+    pub context_status: ContextStatus,
+    pub context_deadline: Option<u64>,
+    pub document_deadlines: UnorderedMap<DocumentId, u64>,
+    pub pending_invitations: UnorderedMap<UserId, InvitationV11>,
+    pub archived_identity_mappings: UnorderedMap<String, Vector<IdentityMappingV11>>,
+    pub shared_identity_index: UnorderedMap<UserId, String>,
+}
+
+/// Move every element of a legacy vector into a new vector of current types
+macro_rules! migrate_vector {
+    ($vector:expr, $what:literal, $convert:expr) => {{
+        let mut vector = $vector;
+        let legacy: Vec<_> = vector
+            .iter()
+            .map_err(|e| MeroDocsError::storage(concat!("Failed to read legacy ", $what), e))?
+            .collect();
+        vector
+            .clear()
+            .map_err(|e| MeroDocsError::storage(concat!("Failed to clear legacy ", $what), e))?;
+
+        let mut migrated = Vector::new();
+        for value in legacy {
+            migrated
+                .push($convert(value))
+                .map_err(|e| MeroDocsError::storage(concat!("Failed to migrate ", $what), e))?;
+        }
+        migrated
+    }};
+}
+
+/// Move every entry of a legacy map into a new map of current types
+macro_rules! migrate_map {
+    ($map:expr, $what:literal, |$value:ident| $convert:expr) => {{
+        let legacy: Vec<_> = $map
+            .entries()
+            .map_err(|e| MeroDocsError::storage(concat!("Failed to read legacy ", $what), e))?
+            .collect();
+        $map.clear()
+            .map_err(|e| MeroDocsError::storage(concat!("Failed to clear legacy ", $what), e))?;
+
+        let mut migrated = UnorderedMap::new();
+        for (key, $value) in legacy {
+            migrated
+                .insert(key, $convert)
+                .map_err(|e| MeroDocsError::storage(concat!("Failed to migrate ", $what), e))?;
+        }
+        migrated
+    }};
+}
+
+impl DocumentChunkV11 {
+    fn migrate(self) -> DocumentChunk {
+        DocumentChunk {
+            text: self.text,
+            embedding: self.embedding,
+            start_position: self.start_position,
+            end_position: self.end_position,
+        }
+    }
+}
+
+impl DocumentInfoV11 {
+    fn migrate(self) -> DocumentInfo {
+        DocumentInfo {
+            id: self.id,
+            name: self.name,
+            hash: self.hash,
+            uploaded_by: self.uploaded_by,
+            uploaded_at: self.uploaded_at,
+            status: self.status,
+            pdf_blob_id: self.pdf_blob_id,
+            size: self.size,
+            embeddings: self.embeddings,
+            extracted_text: self.extracted_text,
+            chunks: self
+                .chunks
+                .map(|chunks| chunks.into_iter().map(DocumentChunkV11::migrate).collect()),
+            required_signers: self.required_signers,
+            signing_mode: self.signing_mode,
+        }
+    }
+}
+
+impl DocumentSignatureV11 {
+    fn migrate(self) -> DocumentSignature {
+        DocumentSignature {
+            signer: self.signer,
+            signed_by: self.signed_by,
+            signed_at: self.signed_at,
+            document_hash: self.document_hash,
+            signature: self.signature,
+        }
+    }
+}
+
+impl DocumentRevisionV11 {
+    fn migrate(self) -> DocumentRevision {
+        DocumentRevision {
+            revision: self.revision,
+            blob_id: self.blob_id,
+            hash: self.hash,
+            size: self.size,
+            author: self.author,
+            created_at: self.created_at,
+            reason: match self.reason {
+                RevisionReasonV11::Upload => RevisionReason::Upload,
+                RevisionReasonV11::Signature => RevisionReason::Signature,
+                RevisionReasonV11::Amendment => RevisionReason::Amendment,
+                RevisionReasonV11::Migrated {
+                    legacy_id,
+                    legacy_hash,
+                } => RevisionReason::Migrated {
+                    legacy_id,
+                    legacy_hash,
+                },
+            },
+        }
+    }
+}
+
+impl ConsentRecordV11 {
+    fn migrate(self) -> ConsentRecord {
+        ConsentRecord {
+            given: self.given,
+            disclosure_version: self.disclosure_version,
+            disclosure_hash: self.disclosure_hash,
+            recorded_at: self.recorded_at,
+        }
+    }
+}
+
+impl InvitationV11 {
+    fn migrate(self) -> Invitation {
+        Invitation {
+            user_id: self.user_id,
+            role: self.role,
+            invited_by: self.invited_by,
+            invited_at: self.invited_at,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+impl ContextMetadataV11 {
+    fn migrate(self) -> ContextMetadata {
+        ContextMetadata {
+            context_id: self.context_id,
+            context_name: self.context_name,
+            role: self.role,
+            joined_at: self.joined_at,
+            private_identity: self.private_identity,
+            shared_identity: self.shared_identity,
+            membership: self.membership,
+            document_count: self.document_count,
+            synced_at: self.synced_at,
+        }
+    }
+}
+
+impl IdentityMappingV11 {
+    fn migrate(self) -> IdentityMapping {
+        IdentityMapping {
+            private_identity: self.private_identity,
+            shared_identity: self.shared_identity,
+            context_id: self.context_id,
+            created_at: self.created_at,
+            left_at: self.left_at,
+        }
+    }
+}
+
+impl MeroDocsStateV11 {
+    /// Treat every stored signature as a full PNG signature; the oldest becomes the default.
+    /// Every other stored value is rewritten into its current type unchanged.
+    pub fn migrate(mut self) -> Result<MeroDocsState, MeroDocsError> {
+        let legacy: Vec<(String, SignatureRecordV11)> = self
+            .signatures
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to read legacy signatures", e))?
+            .collect();
+        self.signatures
+            .clear()
+            .map_err(|e| MeroDocsError::storage("Failed to clear legacy signatures", e))?;
+
+        let oldest = legacy.iter().map(|(_, signature)| signature.id).min();
+        let mut signatures = UnorderedMap::new();
+        for (key, signature) in legacy {
+            signatures
+                .insert(
+                    key,
+                    SignatureRecord {
+                        id: signature.id,
+                        name: signature.name,
+                        blob_id: signature.blob_id,
+                        size: signature.size,
+                        created_at: signature.created_at,
+                        kind: SignatureKind::Signature,
+                        mime_type: "image/png".to_string(),
+                        is_default: Some(signature.id) == oldest,
+                    },
+                )
+                .map_err(|e| MeroDocsError::storage("Failed to migrate signature", e))?;
+        }
+
+        let joined_contexts = migrate_map!(self.joined_contexts, "joined contexts", |metadata| {
+            metadata.migrate()
+        });
+        let identity_mappings =
+            migrate_map!(self.identity_mappings, "identity mappings", |mapping| {
+                mapping.migrate()
+            });
+        let documents = migrate_map!(self.documents, "documents", |document| document.migrate());
+        let document_signatures =
+            migrate_map!(self.document_signatures, "document signatures", |history| {
+                migrate_vector!(
+                    history,
+                    "document signatures",
+                    DocumentSignatureV11::migrate
+                )
+            });
+        let document_revisions =
+            migrate_map!(self.document_revisions, "document revisions", |history| {
+                migrate_vector!(history, "document revisions", DocumentRevisionV11::migrate)
+            });
+        let consents = migrate_map!(self.consents, "consents", |history| {
+            migrate_vector!(history, "consents", ConsentRecordV11::migrate)
+        });
+        let audit_log = migrate_vector!(self.audit_log, "audit log", AuditEntryV11::migrate);
+        let pending_invitations =
+            migrate_map!(self.pending_invitations, "invitations", |invitation| {
+                invitation.migrate()
+            });
+        let archived_identity_mappings = migrate_map!(
+            self.archived_identity_mappings,
+            "archived identity mappings",
+            |history| migrate_vector!(
+                history,
+                "archived identity mappings",
+                IdentityMappingV11::migrate
+            )
+        );
+
+        Ok(MeroDocsState {
+            schema_version: 12,
+            is_private: self.is_private,
+            owner: self.owner,
+            context_name: self.context_name,
+
+            signatures,
+            joined_contexts,
+            identity_mappings,
+            signature_count: self.signature_count,
+            participants: self.participants,
+            documents,
+            document_signatures,
+            document_revisions,
+            document_count: self.document_count,
+            permissions: self.permissions,
+            consents,
+            audit_log,
+            context_status: self.context_status,
+            context_deadline: self.context_deadline,
+            document_deadlines: self.document_deadlines,
+            pending_invitations,
+            archived_identity_mappings,
+            shared_identity_index: self.shared_identity_index,
+        })
+    }
+}
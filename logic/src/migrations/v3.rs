@@ -0,0 +1,106 @@
+//! Schema version 3: audit log entries without hash-chain links
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use super::v11::{
+    AuditEntryV11, DocumentInfoV11, DocumentRevisionV11, DocumentSignatureV11, SignatureRecordV11,
+};
+use super::v4::MeroDocsStateV4;
+use super::v6::ContextMetadataV6;
+use super::v9::IdentityMappingV9;
+use crate::types::hash::Sha256Digest;
+use crate::types::id::{ConsentKey, DocumentId, UserId};
+use crate::{AuditAction, MeroDocsError, PermissionLevel};
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct AuditEntryV3 {
+    pub sequence: u64,
+    pub actor: UserId,
+    pub action: AuditAction,
+    pub timestamp: u64,
+    pub document_id: Option<DocumentId>,
+    pub subject: Option<UserId>,
+    pub document_hash: Option<Sha256Digest>,
+    pub details: Option<String>,
+}
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroDocsStateV3 {
+    pub schema_version: u32,
+    pub is_private: bool,
+    pub owner: UserId,
+    pub context_name: String,
+    pub signatures: UnorderedMap<String, SignatureRecordV11>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadataV6>,
+    pub identity_mappings: UnorderedMap<String, IdentityMappingV9>,
+    pub signature_count: u64,
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<DocumentId, DocumentInfoV11>,
+    pub document_signatures: UnorderedMap<DocumentId, Vector<DocumentSignatureV11>>,
+    pub document_revisions: UnorderedMap<DocumentId, Vector<DocumentRevisionV11>>,
+    pub document_count: u64,
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub consents: UnorderedMap<ConsentKey, bool>,
+    pub audit_log: Vector<AuditEntryV3>,
+}
+
+impl MeroDocsStateV3 {
+    /// Chain the existing audit entries in sequence order
+    pub fn migrate(mut self) -> Result<MeroDocsStateV4, MeroDocsError> {
+        let legacy: Vec<AuditEntryV3> = self
+            .audit_log
+            .iter()
+            .map_err(|e| MeroDocsError::storage("Failed to read legacy audit log", e))?
+            .collect();
+        self.audit_log
+            .clear()
+            .map_err(|e| MeroDocsError::storage("Failed to clear legacy audit log", e))?;
+
+        let mut audit_log = Vector::new();
+        let mut previous_hash = None;
+        for old in legacy {
+            let mut entry = AuditEntryV11 {
+                sequence: old.sequence,
+                actor: old.actor,
+                action: old.action,
+                timestamp: old.timestamp,
+                document_id: old.document_id,
+                subject: old.subject,
+                document_hash: old.document_hash,
+                details: old.details,
+                previous_hash,
+                entry_hash: Sha256Digest::new([0; 32]),
+            };
+            entry.entry_hash = entry.compute_hash();
+            previous_hash = Some(entry.entry_hash);
+
+            audit_log
+                .push(entry)
+                .map_err(|e| MeroDocsError::storage("Failed to migrate audit entry", e))?;
+        }
+
+        Ok(MeroDocsStateV4 {
+            schema_version: 4,
+            is_private: self.is_private,
+            owner: self.owner,
+            context_name: self.context_name,
+
+            signatures: self.signatures,
+            joined_contexts: self.joined_contexts,
+            identity_mappings: self.identity_mappings,
+            signature_count: self.signature_count,
+            participants: self.participants,
+            documents: self.documents,
+            document_signatures: self.document_signatures,
+            document_revisions: self.document_revisions,
+            document_count: self.document_count,
+            permissions: self.permissions,
+            consents: self.consents,
+            audit_log,
+        })
+    }
+}
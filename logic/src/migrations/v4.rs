@@ -0,0 +1,65 @@
+//! Schema version 4: hash-chained audit log, before signing deadlines
+
+use calimero_sdk::borsh::BorshDeserialize;
+#[cfg(test)]
+use calimero_sdk::borsh::BorshSerialize;
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use super::v11::{
+    AuditEntryV11, DocumentInfoV11, DocumentRevisionV11, DocumentSignatureV11, SignatureRecordV11,
+};
+use super::v5::MeroDocsStateV5;
+use super::v6::ContextMetadataV6;
+use super::v9::IdentityMappingV9;
+use crate::types::id::{ConsentKey, DocumentId, UserId};
+use crate::{ContextStatus, MeroDocsError, PermissionLevel};
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroDocsStateV4 {
+    pub schema_version: u32,
+    pub is_private: bool,
+    pub owner: UserId,
+    pub context_name: String,
+    pub signatures: UnorderedMap<String, SignatureRecordV11>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadataV6>,
+    pub identity_mappings: UnorderedMap<String, IdentityMappingV9>,
+    pub signature_count: u64,
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<DocumentId, DocumentInfoV11>,
+    pub document_signatures: UnorderedMap<DocumentId, Vector<DocumentSignatureV11>>,
+    pub document_revisions: UnorderedMap<DocumentId, Vector<DocumentRevisionV11>>,
+    pub document_count: u64,
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub consents: UnorderedMap<ConsentKey, bool>,
+    pub audit_log: Vector<AuditEntryV11>,
+}
+
+impl MeroDocsStateV4 {
+    /// Start without deadlines, in an active context
+    pub fn migrate(self) -> Result<MeroDocsStateV5, MeroDocsError> {
+        Ok(MeroDocsStateV5 {
+            schema_version: 5,
+            is_private: self.is_private,
+            owner: self.owner,
+            context_name: self.context_name,
+
+            signatures: self.signatures,
+            joined_contexts: self.joined_contexts,
+            identity_mappings: self.identity_mappings,
+            signature_count: self.signature_count,
+            participants: self.participants,
+            documents: self.documents,
+            document_signatures: self.document_signatures,
+            document_revisions: self.document_revisions,
+            document_count: self.document_count,
+            permissions: self.permissions,
+            consents: self.consents,
+            audit_log: self.audit_log,
+            context_status: ContextStatus::Active,
+            context_deadline: None,
+            document_deadlines: UnorderedMap::new(),
+        })
+    }
+}
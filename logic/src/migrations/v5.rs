@@ -0,0 +1,93 @@
+//! Schema version 5: signing deadlines, before consent history
+
+use calimero_sdk::borsh::BorshDeserialize;
+#[cfg(test)]
+use calimero_sdk::borsh::BorshSerialize;
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use super::v11::{
+    AuditEntryV11, ConsentRecordV11, DocumentInfoV11, DocumentRevisionV11, DocumentSignatureV11,
+    SignatureRecordV11,
+};
+use super::v6::{ContextMetadataV6, MeroDocsStateV6};
+use super::v9::IdentityMappingV9;
+use crate::types::id::{ConsentKey, DocumentId, UserId};
+use crate::{ContextStatus, MeroDocsError, PermissionLevel};
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroDocsStateV5 {
+    pub schema_version: u32,
+    pub is_private: bool,
+    pub owner: UserId,
+    pub context_name: String,
+    pub signatures: UnorderedMap<String, SignatureRecordV11>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadataV6>,
+    pub identity_mappings: UnorderedMap<String, IdentityMappingV9>,
+    pub signature_count: u64,
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<DocumentId, DocumentInfoV11>,
+    pub document_signatures: UnorderedMap<DocumentId, Vector<DocumentSignatureV11>>,
+    pub document_revisions: UnorderedMap<DocumentId, Vector<DocumentRevisionV11>>,
+    pub document_count: u64,
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub consents: UnorderedMap<ConsentKey, bool>,
+    pub audit_log: Vector<AuditEntryV11>,
+    pub context_status: ContextStatus,
+    pub context_deadline: Option<u64>,
+    pub document_deadlines: UnorderedMap<DocumentId, u64>,
+}
+
+impl MeroDocsStateV5 {
+    /// Turn each stored consent flag into a single-record history without disclosure details
+    pub fn migrate(mut self) -> Result<MeroDocsStateV6, MeroDocsError> {
+        let legacy: Vec<(ConsentKey, bool)> = self
+            .consents
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to read legacy consents", e))?
+            .collect();
+        self.consents
+            .clear()
+            .map_err(|e| MeroDocsError::storage("Failed to clear legacy consents", e))?;
+
+        let mut consents = UnorderedMap::new();
+        for (key, given) in legacy {
+            let mut records = Vector::new();
+            records
+                .push(ConsentRecordV11 {
+                    given,
+                    disclosure_version: None,
+                    disclosure_hash: None,
+                    recorded_at: 0,
+                })
+                .map_err(|e| MeroDocsError::storage("Failed to migrate consent", e))?;
+            consents
+                .insert(key, records)
+                .map_err(|e| MeroDocsError::storage("Failed to migrate consent", e))?;
+        }
+
+        Ok(MeroDocsStateV6 {
+            schema_version: 6,
+            is_private: self.is_private,
+            owner: self.owner,
+            context_name: self.context_name,
+
+            signatures: self.signatures,
+            joined_contexts: self.joined_contexts,
+            identity_mappings: self.identity_mappings,
+            signature_count: self.signature_count,
+            participants: self.participants,
+            documents: self.documents,
+            document_signatures: self.document_signatures,
+            document_revisions: self.document_revisions,
+            document_count: self.document_count,
+            permissions: self.permissions,
+            consents,
+            audit_log: self.audit_log,
+            context_status: self.context_status,
+            context_deadline: self.context_deadline,
+            document_deadlines: self.document_deadlines,
+        })
+    }
+}
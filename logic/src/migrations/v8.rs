@@ -0,0 +1,107 @@
+//! Schema version 8: invitations, before joined-context membership tracking
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use super::v11::{
+    AuditEntryV11, ConsentRecordV11, ContextMetadataV11, DocumentInfoV11, DocumentRevisionV11,
+    DocumentSignatureV11, InvitationV11, SignatureRecordV11,
+};
+use super::v9::{IdentityMappingV9, MeroDocsStateV9};
+use crate::types::id::{ConsentKey, DocumentId, UserId};
+use crate::{ContextStatus, MembershipStatus, MeroDocsError, PermissionLevel};
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct ContextMetadataV8 {
+    pub context_id: String,
+    pub context_name: String,
+    pub role: Option<PermissionLevel>,
+    pub joined_at: u64,
+    pub private_identity: UserId,
+    pub shared_identity: UserId,
+}
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroDocsStateV8 {
+    pub schema_version: u32,
+    pub is_private: bool,
+    pub owner: UserId,
+    pub context_name: String,
+    pub signatures: UnorderedMap<String, SignatureRecordV11>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadataV8>,
+    pub identity_mappings: UnorderedMap<String, IdentityMappingV9>,
+    pub signature_count: u64,
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<DocumentId, DocumentInfoV11>,
+    pub document_signatures: UnorderedMap<DocumentId, Vector<DocumentSignatureV11>>,
+    pub document_revisions: UnorderedMap<DocumentId, Vector<DocumentRevisionV11>>,
+    pub document_count: u64,
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub consents: UnorderedMap<ConsentKey, Vector<ConsentRecordV11>>,
+    pub audit_log: Vector<AuditEntryV11>,
+    pub context_status: ContextStatus,
+    pub context_deadline: Option<u64>,
+    pub document_deadlines: UnorderedMap<DocumentId, u64>,
+    pub pending_invitations: UnorderedMap<UserId, InvitationV11>,
+}
+
+impl MeroDocsStateV8 {
+    /// Treat every joined context as an active membership that has never been synced
+    pub fn migrate(mut self) -> Result<MeroDocsStateV9, MeroDocsError> {
+        let legacy: Vec<(String, ContextMetadataV8)> = self
+            .joined_contexts
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to read legacy joined contexts", e))?
+            .collect();
+        self.joined_contexts
+            .clear()
+            .map_err(|e| MeroDocsError::storage("Failed to clear legacy joined contexts", e))?;
+
+        let mut joined_contexts = UnorderedMap::new();
+        for (context_id, metadata) in legacy {
+            joined_contexts
+                .insert(
+                    context_id,
+                    ContextMetadataV11 {
+                        context_id: metadata.context_id,
+                        context_name: metadata.context_name,
+                        role: metadata.role,
+                        joined_at: metadata.joined_at,
+                        private_identity: metadata.private_identity,
+                        shared_identity: metadata.shared_identity,
+                        membership: MembershipStatus::Active,
+                        document_count: None,
+                        synced_at: None,
+                    },
+                )
+                .map_err(|e| MeroDocsError::storage("Failed to migrate joined context", e))?;
+        }
+
+        Ok(MeroDocsStateV9 {
+            schema_version: 9,
+            is_private: self.is_private,
+            owner: self.owner,
+            context_name: self.context_name,
+
+            signatures: self.signatures,
+            joined_contexts,
+            identity_mappings: self.identity_mappings,
+            signature_count: self.signature_count,
+            participants: self.participants,
+            documents: self.documents,
+            document_signatures: self.document_signatures,
+            document_revisions: self.document_revisions,
+            document_count: self.document_count,
+            permissions: self.permissions,
+            consents: self.consents,
+            audit_log: self.audit_log,
+            context_status: self.context_status,
+            context_deadline: self.context_deadline,
+            document_deadlines: self.document_deadlines,
+            pending_invitations: self.pending_invitations,
+        })
+    }
+}
@@ -0,0 +1,119 @@
+//! Schema version 9: joined-context membership tracking, before identity mapping history
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use super::v10::MeroDocsStateV10;
+use super::v11::{
+    AuditEntryV11, ConsentRecordV11, ContextMetadataV11, DocumentInfoV11, DocumentRevisionV11,
+    DocumentSignatureV11, IdentityMappingV11, InvitationV11, SignatureRecordV11,
+};
+use crate::types::id::{ConsentKey, DocumentId, UserId};
+use crate::{ContextStatus, MembershipStatus, MeroDocsError, PermissionLevel};
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct IdentityMappingV9 {
+    pub private_identity: UserId,
+    pub shared_identity: UserId,
+    pub context_id: String,
+    pub created_at: u64,
+}
+
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroDocsStateV9 {
+    pub schema_version: u32,
+    pub is_private: bool,
+    pub owner: UserId,
+    pub context_name: String,
+    pub signatures: UnorderedMap<String, SignatureRecordV11>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadataV11>,
+    pub identity_mappings: UnorderedMap<String, IdentityMappingV9>,
+    pub signature_count: u64,
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<DocumentId, DocumentInfoV11>,
+    pub document_signatures: UnorderedMap<DocumentId, Vector<DocumentSignatureV11>>,
+    pub document_revisions: UnorderedMap<DocumentId, Vector<DocumentRevisionV11>>,
+    pub document_count: u64,
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub consents: UnorderedMap<ConsentKey, Vector<ConsentRecordV11>>,
+    pub audit_log: Vector<AuditEntryV11>,
+    pub context_status: ContextStatus,
+    pub context_deadline: Option<u64>,
+    pub document_deadlines: UnorderedMap<DocumentId, u64>,
+    pub pending_invitations: UnorderedMap<UserId, InvitationV11>,
+}
+
+impl MeroDocsStateV9 {
+    /// Keep the mappings of contexts still joined and archive those left behind by
+    /// `leave_shared_context`; their `left_at` is unknown and recorded as 0
+    pub fn migrate(mut self) -> Result<MeroDocsStateV10, MeroDocsError> {
+        let legacy: Vec<(String, IdentityMappingV9)> = self
+            .identity_mappings
+            .entries()
+            .map_err(|e| MeroDocsError::storage("Failed to read legacy identity mappings", e))?
+            .collect();
+        self.identity_mappings
+            .clear()
+            .map_err(|e| MeroDocsError::storage("Failed to clear legacy identity mappings", e))?;
+
+        let mut identity_mappings = UnorderedMap::new();
+        let mut archived_identity_mappings = UnorderedMap::new();
+        for (context_id, mapping) in legacy {
+            let joined = self
+                .joined_contexts
+                .get(&context_id)
+                .map_err(|e| MeroDocsError::storage("Failed to get joined context", e))?
+                .is_some_and(|metadata| metadata.membership == MembershipStatus::Active);
+
+            let mut mapping = IdentityMappingV11 {
+                private_identity: mapping.private_identity,
+                shared_identity: mapping.shared_identity,
+                context_id: mapping.context_id,
+                created_at: mapping.created_at,
+                left_at: None,
+            };
+            if joined {
+                identity_mappings
+                    .insert(context_id, mapping)
+                    .map_err(|e| MeroDocsError::storage("Failed to migrate identity mapping", e))?;
+            } else {
+                mapping.left_at = Some(0);
+                let mut history = Vector::new();
+                history
+                    .push(mapping)
+                    .map_err(|e| MeroDocsError::storage("Failed to migrate identity mapping", e))?;
+                archived_identity_mappings
+                    .insert(context_id, history)
+                    .map_err(|e| MeroDocsError::storage("Failed to migrate identity mapping", e))?;
+            }
+        }
+
+        Ok(MeroDocsStateV10 {
+            schema_version: 10,
+            is_private: self.is_private,
+            owner: self.owner,
+            context_name: self.context_name,
+
+            signatures: self.signatures,
+            joined_contexts: self.joined_contexts,
+            identity_mappings,
+            signature_count: self.signature_count,
+            participants: self.participants,
+            documents: self.documents,
+            document_signatures: self.document_signatures,
+            document_revisions: self.document_revisions,
+            document_count: self.document_count,
+            permissions: self.permissions,
+            consents: self.consents,
+            audit_log: self.audit_log,
+            context_status: self.context_status,
+            context_deadline: self.context_deadline,
+            document_deadlines: self.document_deadlines,
+            pending_invitations: self.pending_invitations,
+            archived_identity_mappings,
+        })
+    }
+}
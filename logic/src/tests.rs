@@ -0,0 +1,118 @@
+//! Guards that run before any call into the host, so they can be exercised without a runtime.
+
+use calimero_storage::collections::{UnorderedMap, UnorderedSet, Vector};
+
+use crate::types::id::{DocumentId, UserId};
+use crate::{migrations, ContextStatus, MeroDocsError, MeroDocsState, PermissionLevel};
+
+const OWNER: UserId = UserId::new([1; 32]);
+const INVITEE: UserId = UserId::new([2; 32]);
+
+const CONTEXT_ID: &str = "shared-context";
+const BLOB_ID: &str = "11111111111111111111111111111111";
+const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+/// A shared context whose deadline has been swept
+fn expired_context() -> MeroDocsState {
+    let mut state = MeroDocsState {
+        schema_version: migrations::SCHEMA_VERSION,
+        is_private: false,
+        owner: OWNER,
+        context_name: "contracts".to_string(),
+        signatures: UnorderedMap::new(),
+        joined_contexts: UnorderedMap::new(),
+        identity_mappings: UnorderedMap::new(),
+        signature_count: 0,
+        participants: UnorderedSet::new(),
+        documents: UnorderedMap::new(),
+        document_signatures: UnorderedMap::new(),
+        document_revisions: UnorderedMap::new(),
+        document_count: 0,
+        permissions: UnorderedMap::new(),
+        consents: UnorderedMap::new(),
+        audit_log: Vector::new(),
+        context_status: ContextStatus::Expired,
+        context_deadline: Some(1_700_000_000_000_000_000),
+        document_deadlines: UnorderedMap::new(),
+        pending_invitations: UnorderedMap::new(),
+        archived_identity_mappings: UnorderedMap::new(),
+        shared_identity_index: UnorderedMap::new(),
+    };
+    state.participants.insert(OWNER).unwrap();
+    state
+        .permissions
+        .insert(OWNER, PermissionLevel::Admin)
+        .unwrap();
+    state
+}
+
+fn assert_expired<T: std::fmt::Debug>(result: Result<T, MeroDocsError>) {
+    assert_eq!(result.unwrap_err(), MeroDocsError::ContextExpired);
+}
+
+#[test]
+fn expired_context_refuses_uploads() {
+    let mut state = expired_context();
+
+    assert_expired(state.upload_document(
+        CONTEXT_ID.to_string(),
+        "contract.pdf".to_string(),
+        HASH.to_string(),
+        BLOB_ID.to_string(),
+        1024,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ));
+    assert_expired(state.amend_document(
+        CONTEXT_ID.to_string(),
+        DocumentId::new([5; 32]),
+        BLOB_ID.to_string(),
+        1024,
+        HASH.to_string(),
+    ));
+}
+
+#[test]
+fn expired_context_refuses_invitations() {
+    let mut state = expired_context();
+
+    assert_expired(state.invite_participant(
+        CONTEXT_ID.to_string(),
+        INVITEE,
+        PermissionLevel::Sign,
+        None,
+    ));
+    assert_expired(state.accept_invitation(CONTEXT_ID.to_string()));
+}
+
+#[test]
+fn expired_context_refuses_signing() {
+    let mut state = expired_context();
+    let document_id = DocumentId::new([5; 32]);
+
+    assert_expired(state.set_consent(document_id, None, None));
+    assert_expired(state.withdraw_consent(document_id));
+    assert_expired(state.sign_document(
+        CONTEXT_ID.to_string(),
+        document_id,
+        BLOB_ID.to_string(),
+        1024,
+        HASH.to_string(),
+        None,
+        None,
+    ));
+}
+
+#[test]
+fn expired_context_refuses_publishing_and_deadlines() {
+    let mut state = expired_context();
+    let document_id = DocumentId::new([5; 32]);
+
+    assert_expired(state.publish_document(CONTEXT_ID.to_string(), document_id));
+    assert_expired(state.set_document_deadline(CONTEXT_ID.to_string(), document_id, None));
+    assert_expired(state.set_context_deadline(None));
+}
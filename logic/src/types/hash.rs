@@ -0,0 +1,99 @@
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
+use std::borrow::Cow;
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_sdk::serde::{self, de, Deserialize, Serialize};
+
+/// SHA-256 digest, rendered as 64 lowercase hex characters
+#[derive(
+    Eq,
+    Ord,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    PartialOrd,
+    BorshDeserialize,
+    BorshSerialize,
+    std::hash::Hash,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[repr(transparent)]
+pub struct Sha256Digest([u8; 32]);
+
+impl Sha256Digest {
+    /// Stands in for the digest of a migrated document whose legacy hash was not a SHA-256 digest
+    pub const UNVERIFIED: Self = Self([0; 32]);
+
+    pub const fn new(digest: [u8; 32]) -> Self {
+        Self(digest)
+    }
+}
+
+impl FromStr for Sha256Digest {
+    type Err = hex::FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut buf = [0; 32];
+
+        hex::decode_to_slice(s, &mut buf)?;
+
+        Ok(Self(buf))
+    }
+}
+
+impl AsRef<[u8]> for Sha256Digest {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Deref for Sha256Digest {
+    type Target = [u8; 32];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for Sha256Digest {
+    fn from(digest: [u8; 32]) -> Self {
+        Self::new(digest)
+    }
+}
+
+impl fmt::Display for Sha256Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0; 64];
+
+        hex::encode_to_slice(self.0, &mut buf).expect("buffer sized for a 32 byte digest");
+
+        f.pad(core::str::from_utf8(&buf).unwrap())
+    }
+}
+
+impl Serialize for Sha256Digest {
+    fn serialize<O>(&self, serializer: O) -> Result<O::Ok, O::Error>
+    where
+        O: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Sha256Digest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(crate = "calimero_sdk::serde")]
+        struct Container<'a>(#[serde(borrow)] Cow<'a, str>);
+
+        let encoded = Container::deserialize(deserializer)?;
+
+        Self::from_str(&encoded.0).map_err(de::Error::custom)
+    }
+}
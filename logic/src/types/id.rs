@@ -224,3 +224,20 @@ pub(crate) use define;
 define!(pub UserId<32, 44>);
 
 define!(pub BlobId<32, 44>);
+
+define!(pub DocumentId<32, 44>);
+
+define!(pub Ed25519Signature<64, 88>);
+
+define!(pub ConsentKey<64, 88>);
+
+impl ConsentKey {
+    /// Composite key identifying a participant's consent to a document
+    pub fn for_document(user_id: &UserId, document_id: &DocumentId) -> Self {
+        let mut key = [0; 64];
+        key[..32].copy_from_slice(user_id.as_ref());
+        key[32..].copy_from_slice(document_id.as_ref());
+
+        Self::from_id(Id::new(key))
+    }
+}
@@ -0,0 +1,85 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Event emitted by a shared context, tagged with the context that emitted it
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ContextEvent {
+    pub context_id: String,
+    /// Position of the event in the context's event stream; a replayed event keeps it
+    pub sequence: u64,
+    pub event: RelayEvent,
+}
+
+/// Subset of `MeroDocsEvent` that has a counterpart in the registry.
+///
+/// Deserializes from the JSON emitted by the node (`{ "kind": ..., "data": ... }`); IDs and
+/// hashes arrive in their string encodings.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "kind", content = "data")]
+pub enum RelayEvent {
+    DocumentUploaded {
+        id: String,
+        hash: String,
+        uploaded_by: String,
+    },
+    ParticipantJoined {
+        user_id: String,
+    },
+    ConsentGiven {
+        document_id: String,
+        user_id: String,
+        #[serde(default)]
+        disclosure_hash: Option<String>,
+    },
+    ConsentWithdrawn {
+        document_id: String,
+        user_id: String,
+    },
+    DocumentSigned {
+        document_id: String,
+        signer: String,
+    },
+    DocumentCompleted {
+        document_id: String,
+        final_hash: String,
+    },
+}
+
+impl RelayEvent {
+    const KINDS: &'static [&'static str] = &[
+        "DocumentUploaded",
+        "ParticipantJoined",
+        "ConsentGiven",
+        "ConsentWithdrawn",
+        "DocumentSigned",
+        "DocumentCompleted",
+    ];
+}
+
+#[derive(Deserialize)]
+struct RawContextEvent {
+    context_id: String,
+    sequence: u64,
+    event: Value,
+}
+
+impl ContextEvent {
+    /// Parse an event as emitted by the node; kinds without a registry counterpart yield `None`
+    pub fn from_json(json: &str) -> Result<Option<Self>, serde_json::Error> {
+        let raw: RawContextEvent = serde_json::from_str(json)?;
+        let relayed = raw
+            .event
+            .get("kind")
+            .and_then(Value::as_str)
+            .is_some_and(|kind| RelayEvent::KINDS.contains(&kind));
+        if !relayed {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            context_id: raw.context_id,
+            sequence: raw.sequence,
+            event: serde_json::from_value(raw.event)?,
+        }))
+    }
+}
@@ -0,0 +1,365 @@
+//! Relays MeroDocs shared-context events to the `merodocs_registry` canister, so the on-chain
+//! audit trail follows the one kept in the context.
+//!
+//! Every registry call derived from an event carries an idempotency key. Keys of delivered calls
+//! are remembered, so replaying an event stream never submits the same call twice; calls the
+//! registry reports as already applied count as delivered. A participant may give and withdraw
+//! consent to the same document repeatedly, so those keys include the event's sequence.
+
+use core::fmt;
+use std::collections::{HashMap, HashSet};
+use std::thread;
+use std::time::Duration;
+
+mod event;
+mod local;
+mod registry;
+
+pub use event::{ContextEvent, RelayEvent};
+pub use local::{LocalContext, LocalDocument, LocalRegistry};
+pub use registry::{
+    Registry, RegistryError, ALREADY_PARTICIPANT, ALREADY_SIGNED, FINAL_HASH_RECORDED,
+};
+
+/// How often and how patiently a failed registry call is retried
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Registry call that could not be delivered
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelayError {
+    pub idempotency_key: String,
+    pub attempts: u32,
+    pub error: RegistryError,
+}
+
+impl fmt::Display for RelayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to deliver {} after {} attempt(s): {}",
+            self.idempotency_key, self.attempts, self.error
+        )
+    }
+}
+
+impl std::error::Error for RelayError {}
+
+/// A single registry call derived from an event
+#[derive(Debug, Clone, PartialEq)]
+enum Call<'a> {
+    CreateContext,
+    AddParticipant {
+        user_id: &'a str,
+    },
+    UploadDocument {
+        document_id: &'a str,
+        document_hash: &'a str,
+    },
+    RecordConsent {
+        document_id: &'a str,
+        user_id: &'a str,
+        consent_text_hash: Option<&'a str>,
+        sequence: u64,
+    },
+    WithdrawConsent {
+        document_id: &'a str,
+        user_id: &'a str,
+        sequence: u64,
+    },
+    SignDocument {
+        document_id: &'a str,
+        signer: &'a str,
+    },
+    RecordFinalHash {
+        document_id: &'a str,
+        hash: &'a str,
+    },
+}
+
+impl Call<'_> {
+    fn idempotency_key(&self, context_id: &str) -> String {
+        match self {
+            Call::CreateContext => format!("context:{}", context_id),
+            Call::AddParticipant { user_id } => format!("participant:{}:{}", context_id, user_id),
+            Call::UploadDocument { document_id, .. } => {
+                format!("upload:{}:{}", context_id, document_id)
+            }
+            Call::RecordConsent {
+                document_id,
+                user_id,
+                sequence,
+                ..
+            } => format!(
+                "consent:{}:{}:{}:{}",
+                context_id, document_id, user_id, sequence
+            ),
+            Call::WithdrawConsent {
+                document_id,
+                user_id,
+                sequence,
+            } => format!(
+                "withdraw:{}:{}:{}:{}",
+                context_id, document_id, user_id, sequence
+            ),
+            Call::SignDocument {
+                document_id,
+                signer,
+            } => format!("sign:{}:{}:{}", context_id, document_id, signer),
+            Call::RecordFinalHash { document_id, .. } => {
+                format!("final:{}:{}", context_id, document_id)
+            }
+        }
+    }
+
+    /// Whether the registry rejected this call because an earlier attempt already applied it.
+    ///
+    /// Only the exact duplicate of each call counts; any other rejection, such as withdrawing a
+    /// consent that was never recorded, is reported.
+    fn is_already_applied(&self, error: &RegistryError) -> bool {
+        match self {
+            Call::CreateContext | Call::UploadDocument { .. } => {
+                *error == RegistryError::AlreadyExists
+            }
+            Call::AddParticipant { .. } => error.is_conflict(ALREADY_PARTICIPANT),
+            Call::SignDocument { .. } => error.is_conflict(ALREADY_SIGNED),
+            Call::RecordFinalHash { .. } => error.is_conflict(FINAL_HASH_RECORDED),
+            // Recording a consent overwrites the previous one, so it never reports a duplicate
+            Call::RecordConsent { .. } | Call::WithdrawConsent { .. } => false,
+        }
+    }
+}
+
+/// Submits the registry calls matching each context event.
+///
+/// The relay's own principal creates the registry contexts and therefore administers them: it
+/// uploads documents, adds participants and records final hashes. It does not sign, so contexts
+/// are created with `admin_signs` off and a document is fully signed once every participant has.
+///
+/// Events name participants by their context identity. Each is mapped to the registry principal
+/// the participant is added as, and consents and signatures are submitted as that principal; the
+/// `Registry` implementation holds the keys to sign as every mapped principal. Calls for an
+/// identity without a mapping fail with [`RegistryError::UnmappedIdentity`] until one is added.
+pub struct Relay<R> {
+    registry: R,
+    identity: String,
+    principals: HashMap<String, String>,
+    retry: RetryPolicy,
+    delivered: HashSet<String>,
+}
+
+impl<R: Registry> Relay<R> {
+    pub fn new(registry: R, identity: impl Into<String>) -> Self {
+        Self {
+            registry,
+            identity: identity.into(),
+            principals: HashMap::new(),
+            retry: RetryPolicy::default(),
+            delivered: HashSet::new(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Make the calls of the participant known by `context_identity` as `principal`
+    pub fn with_principal(
+        mut self,
+        context_identity: impl Into<String>,
+        principal: impl Into<String>,
+    ) -> Self {
+        let _ = self
+            .principals
+            .insert(context_identity.into(), principal.into());
+        self
+    }
+
+    /// Resume with the keys delivered by an earlier run
+    pub fn with_delivered(mut self, keys: impl IntoIterator<Item = String>) -> Self {
+        self.delivered.extend(keys);
+        self
+    }
+
+    /// Idempotency keys of every call delivered so far, for persisting across restarts
+    pub fn delivered(&self) -> impl Iterator<Item = &str> {
+        self.delivered.iter().map(String::as_str)
+    }
+
+    pub fn registry(&self) -> &R {
+        &self.registry
+    }
+
+    pub fn registry_mut(&mut self) -> &mut R {
+        &mut self.registry
+    }
+
+    pub fn into_registry(self) -> R {
+        self.registry
+    }
+
+    /// Submit the registry calls for one event, stopping at the first one that cannot be delivered
+    pub fn handle(&mut self, event: &ContextEvent) -> Result<(), RelayError> {
+        let context_id = event.context_id.as_str();
+        for call in calls_for(event) {
+            self.deliver(context_id, call)?;
+        }
+        Ok(())
+    }
+
+    fn deliver(&mut self, context_id: &str, call: Call<'_>) -> Result<(), RelayError> {
+        let idempotency_key = call.idempotency_key(context_id);
+        if self.delivered.contains(&idempotency_key) {
+            return Ok(());
+        }
+
+        let mut backoff = self.retry.initial_backoff;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match self.submit(context_id, &call) {
+                Ok(()) => break,
+                Err(error) if call.is_already_applied(&error) => break,
+                Err(error) if error.is_retryable() && attempts < self.retry.max_attempts => {
+                    thread::sleep(backoff);
+                    backoff = (backoff * 2).min(self.retry.max_backoff);
+                }
+                Err(error) => {
+                    return Err(RelayError {
+                        idempotency_key,
+                        attempts,
+                        error,
+                    })
+                }
+            }
+        }
+
+        let _ = self.delivered.insert(idempotency_key);
+        Ok(())
+    }
+
+    fn submit(&mut self, context_id: &str, call: &Call<'_>) -> Result<(), RegistryError> {
+        let identity = self.identity.as_str();
+        let principals = &self.principals;
+        let principal = |user_id: &str| {
+            principals
+                .get(user_id)
+                .map(String::as_str)
+                .ok_or_else(|| RegistryError::UnmappedIdentity(user_id.to_string()))
+        };
+
+        match *call {
+            Call::CreateContext => self.registry.create_context(identity, context_id, false),
+            Call::AddParticipant { user_id } => {
+                self.registry
+                    .add_participant_to_context(identity, context_id, principal(user_id)?)
+            }
+            Call::UploadDocument {
+                document_id,
+                document_hash,
+            } => self.registry.upload_document_to_context(
+                identity,
+                context_id,
+                document_id,
+                document_hash,
+            ),
+            Call::RecordConsent {
+                document_id,
+                user_id,
+                consent_text_hash,
+                ..
+            } => self.registry.record_consent_for_context(
+                principal(user_id)?,
+                context_id,
+                document_id,
+                consent_text_hash,
+            ),
+            Call::WithdrawConsent {
+                document_id,
+                user_id,
+                ..
+            } => self
+                .registry
+                .withdraw_consent(principal(user_id)?, context_id, document_id),
+            Call::SignDocument {
+                document_id,
+                signer,
+            } => self.registry.sign_document(principal(signer)?, document_id),
+            Call::RecordFinalHash { document_id, hash } => {
+                self.registry.record_final_hash(identity, document_id, hash)
+            }
+        }
+    }
+}
+
+/// Registry calls for an event, in order; each is preceded by the calls it depends on
+fn calls_for(event: &ContextEvent) -> Vec<Call<'_>> {
+    let sequence = event.sequence;
+    match &event.event {
+        RelayEvent::DocumentUploaded { id, hash, .. } => vec![
+            Call::CreateContext,
+            Call::UploadDocument {
+                document_id: id,
+                document_hash: hash,
+            },
+        ],
+        RelayEvent::ParticipantJoined { user_id } => {
+            vec![Call::CreateContext, Call::AddParticipant { user_id }]
+        }
+        RelayEvent::ConsentGiven {
+            document_id,
+            user_id,
+            disclosure_hash,
+        } => vec![
+            Call::CreateContext,
+            Call::AddParticipant { user_id },
+            Call::RecordConsent {
+                document_id,
+                user_id,
+                consent_text_hash: disclosure_hash.as_deref(),
+                sequence,
+            },
+        ],
+        RelayEvent::ConsentWithdrawn {
+            document_id,
+            user_id,
+        } => vec![Call::WithdrawConsent {
+            document_id,
+            user_id,
+            sequence,
+        }],
+        RelayEvent::DocumentCompleted {
+            document_id,
+            final_hash,
+        } => vec![Call::RecordFinalHash {
+            document_id,
+            hash: final_hash,
+        }],
+        RelayEvent::DocumentSigned {
+            document_id,
+            signer,
+        } => vec![
+            Call::CreateContext,
+            Call::AddParticipant { user_id: signer },
+            Call::SignDocument {
+                document_id,
+                signer,
+            },
+        ],
+    }
+}
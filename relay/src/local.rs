@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use crate::registry::{
+    Registry, RegistryError, ALREADY_PARTICIPANT, ALREADY_SIGNED, FINAL_HASH_RECORDED,
+};
+
+/// In-memory stand-in for the registry canister, applying the same authorization rules
+#[derive(Debug, Default)]
+pub struct LocalRegistry {
+    contexts: HashMap<String, LocalContext>,
+    documents: HashMap<String, LocalDocument>,
+    consents: HashMap<(String, String), Option<String>>, // (user_id, document_id) -> text hash
+    pending_failures: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalContext {
+    pub admin_id: String,
+    pub admin_signs: bool,
+    pub participants: Vec<String>,
+    pub expired: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalDocument {
+    pub context_id: String,
+    pub original_hash: String,
+    pub current_signers: Vec<String>,
+    pub fully_signed: bool,
+    pub final_hash: Option<String>,
+    pub expired: bool,
+}
+
+impl LocalRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail the next `count` calls with a transport error before they reach the registry
+    pub fn fail_next(&mut self, count: u32) {
+        self.pending_failures = count;
+    }
+
+    /// Let the deadline of a context pass, as the canister's overdue sweep would
+    pub fn expire_context(&mut self, context_id: &str) {
+        if let Some(context) = self.contexts.get_mut(context_id) {
+            context.expired = true;
+        }
+    }
+
+    /// Let the signing deadline of a document pass, as the canister's overdue sweep would
+    pub fn expire_document(&mut self, document_id: &str) {
+        if let Some(document) = self.documents.get_mut(document_id) {
+            document.expired = true;
+        }
+    }
+
+    pub fn context(&self, context_id: &str) -> Option<&LocalContext> {
+        self.contexts.get(context_id)
+    }
+
+    pub fn document(&self, document_id: &str) -> Option<&LocalDocument> {
+        self.documents.get(document_id)
+    }
+
+    pub fn has_consented(&self, user_id: &str, document_id: &str) -> bool {
+        self.consents
+            .contains_key(&(user_id.to_string(), document_id.to_string()))
+    }
+
+    fn reach(&mut self) -> Result<(), RegistryError> {
+        if self.pending_failures > 0 {
+            self.pending_failures -= 1;
+            return Err(RegistryError::Transport(
+                "simulated transport failure".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Consents and signatures are refused once the document or its context has expired
+    fn ensure_not_expired(&self, document_id: &str) -> Result<(), RegistryError> {
+        let expired = self.documents.get(document_id).is_some_and(|document| {
+            document.expired
+                || self
+                    .contexts
+                    .get(&document.context_id)
+                    .is_some_and(|context| context.expired)
+        });
+        if expired {
+            return Err(RegistryError::Expired);
+        }
+        Ok(())
+    }
+
+    /// Principals that must sign every document of a context before it is fully signed
+    fn required_signers(&self, context_id: &str) -> Vec<String> {
+        let Some(context) = self.contexts.get(context_id) else {
+            return Vec::new();
+        };
+
+        let mut required_signers = context.participants.clone();
+        if context.admin_signs {
+            required_signers.push(context.admin_id.clone());
+        }
+        required_signers
+    }
+
+    fn is_participant(&self, context_id: &str, user_id: &str) -> bool {
+        self.contexts.get(context_id).is_some_and(|context| {
+            context.admin_id == user_id || context.participants.iter().any(|p| p == user_id)
+        })
+    }
+}
+
+impl Registry for LocalRegistry {
+    fn create_context(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        admin_signs: bool,
+    ) -> Result<(), RegistryError> {
+        self.reach()?;
+        if self.contexts.contains_key(context_id) {
+            return Err(RegistryError::AlreadyExists);
+        }
+
+        self.contexts.insert(
+            context_id.to_string(),
+            LocalContext {
+                admin_id: caller.to_string(),
+                admin_signs,
+                participants: Vec::new(),
+                expired: false,
+            },
+        );
+        Ok(())
+    }
+
+    fn add_participant_to_context(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        participant_id: &str,
+    ) -> Result<(), RegistryError> {
+        self.reach()?;
+        let context = self
+            .contexts
+            .get_mut(context_id)
+            .ok_or(RegistryError::ContextNotFound)?;
+        if context.admin_id != caller {
+            return Err(RegistryError::Unauthorized);
+        }
+        if context.admin_id == participant_id
+            || context.participants.iter().any(|p| p == participant_id)
+        {
+            return Err(RegistryError::UpdateConflict(
+                ALREADY_PARTICIPANT.to_string(),
+            ));
+        }
+
+        context.participants.push(participant_id.to_string());
+        Ok(())
+    }
+
+    fn upload_document_to_context(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        document_id: &str,
+        document_hash: &str,
+    ) -> Result<(), RegistryError> {
+        self.reach()?;
+        let is_admin = self
+            .contexts
+            .get(context_id)
+            .is_some_and(|context| context.admin_id == caller);
+        if !is_admin {
+            return Err(RegistryError::Unauthorized);
+        }
+        if self.documents.contains_key(document_id) {
+            return Err(RegistryError::AlreadyExists);
+        }
+
+        self.documents.insert(
+            document_id.to_string(),
+            LocalDocument {
+                context_id: context_id.to_string(),
+                original_hash: document_hash.to_string(),
+                current_signers: Vec::new(),
+                fully_signed: false,
+                final_hash: None,
+                expired: false,
+            },
+        );
+        Ok(())
+    }
+
+    fn record_consent_for_context(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        document_id: &str,
+        consent_text_hash: Option<&str>,
+    ) -> Result<(), RegistryError> {
+        self.reach()?;
+        if !self.is_participant(context_id, caller) {
+            return Err(RegistryError::Unauthorized);
+        }
+        let in_context = self
+            .documents
+            .get(document_id)
+            .is_some_and(|document| document.context_id == context_id);
+        if !in_context {
+            return Err(RegistryError::NotFound);
+        }
+        self.ensure_not_expired(document_id)?;
+
+        self.consents.insert(
+            (caller.to_string(), document_id.to_string()),
+            consent_text_hash.map(str::to_string),
+        );
+        Ok(())
+    }
+
+    fn withdraw_consent(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        document_id: &str,
+    ) -> Result<(), RegistryError> {
+        self.reach()?;
+        if !self.is_participant(context_id, caller) {
+            return Err(RegistryError::Unauthorized);
+        }
+        let document = self
+            .documents
+            .get(document_id)
+            .filter(|document| document.context_id == context_id)
+            .ok_or(RegistryError::NotFound)?;
+        if document.current_signers.iter().any(|s| s == caller) {
+            return Err(RegistryError::UpdateConflict(
+                "User has already signed this document.".to_string(),
+            ));
+        }
+
+        self.consents
+            .remove(&(caller.to_string(), document_id.to_string()))
+            .map(|_| ())
+            .ok_or_else(|| {
+                RegistryError::UpdateConflict(
+                    "User has not consented to this document.".to_string(),
+                )
+            })
+    }
+
+    fn sign_document(&mut self, caller: &str, document_id: &str) -> Result<(), RegistryError> {
+        self.reach()?;
+        let context_id = self
+            .documents
+            .get(document_id)
+            .map(|document| document.context_id.clone())
+            .ok_or(RegistryError::NotFound)?;
+        if !self.is_participant(&context_id, caller) {
+            return Err(RegistryError::Unauthorized);
+        }
+        let (already_signed, fully_signed) = self
+            .documents
+            .get(document_id)
+            .map(|document| {
+                let already_signed = document.current_signers.iter().any(|s| s == caller);
+                (already_signed, document.fully_signed)
+            })
+            .ok_or(RegistryError::NotFound)?;
+        if already_signed {
+            return Err(RegistryError::UpdateConflict(ALREADY_SIGNED.to_string()));
+        }
+        self.ensure_not_expired(document_id)?;
+        if fully_signed {
+            return Err(RegistryError::DocumentNotReady);
+        }
+        if !self.has_consented(caller, document_id) {
+            return Err(RegistryError::ConsentRequired);
+        }
+
+        let required_signers = self.required_signers(&context_id);
+        let document = self
+            .documents
+            .get_mut(document_id)
+            .ok_or(RegistryError::NotFound)?;
+
+        document.current_signers.push(caller.to_string());
+        document.fully_signed = required_signers
+            .iter()
+            .all(|signer| document.current_signers.contains(signer));
+        Ok(())
+    }
+
+    fn record_final_hash(
+        &mut self,
+        caller: &str,
+        document_id: &str,
+        hash: &str,
+    ) -> Result<(), RegistryError> {
+        self.reach()?;
+        let document = self
+            .documents
+            .get_mut(document_id)
+            .ok_or(RegistryError::NotFound)?;
+        let is_admin = self
+            .contexts
+            .get(&document.context_id)
+            .is_some_and(|context| context.admin_id == caller);
+        if !is_admin {
+            return Err(RegistryError::Unauthorized);
+        }
+        if document.final_hash.is_some() {
+            return Err(RegistryError::UpdateConflict(
+                FINAL_HASH_RECORDED.to_string(),
+            ));
+        }
+
+        document.final_hash = Some(hash.to_string());
+        Ok(())
+    }
+}
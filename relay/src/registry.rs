@@ -0,0 +1,108 @@
+use core::fmt;
+
+/// `UpdateConflict` reasons the canister gives for a call that repeats one already applied
+pub const ALREADY_PARTICIPANT: &str = "User is already a participant in this context.";
+pub const ALREADY_SIGNED: &str = "User has already signed this document.";
+pub const FINAL_HASH_RECORDED: &str = "Final hash has already been recorded.";
+
+/// Update calls of the `merodocs_registry` canister used by the relay.
+///
+/// `caller` is the registry principal the call is made as; the canister authorizes every call by
+/// its caller, so an implementation signs each call with the matching identity.
+pub trait Registry {
+    /// `admin_signs` decides whether the caller, as the context admin, must sign every document
+    fn create_context(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        admin_signs: bool,
+    ) -> Result<(), RegistryError>;
+
+    fn add_participant_to_context(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        participant_id: &str,
+    ) -> Result<(), RegistryError>;
+
+    fn upload_document_to_context(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        document_id: &str,
+        document_hash: &str,
+    ) -> Result<(), RegistryError>;
+
+    fn record_consent_for_context(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        document_id: &str,
+        consent_text_hash: Option<&str>,
+    ) -> Result<(), RegistryError>;
+
+    fn withdraw_consent(
+        &mut self,
+        caller: &str,
+        context_id: &str,
+        document_id: &str,
+    ) -> Result<(), RegistryError>;
+
+    fn sign_document(&mut self, caller: &str, document_id: &str) -> Result<(), RegistryError>;
+
+    fn record_final_hash(
+        &mut self,
+        caller: &str,
+        document_id: &str,
+        hash: &str,
+    ) -> Result<(), RegistryError>;
+}
+
+/// Error returned by a registry call: the canister's `Error`, or a failure to make or deliver it
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryError {
+    InvalidInput(String),
+    NotFound,
+    AlreadyExists,
+    UpdateConflict(String),
+    Unauthorized,
+    DocumentNotReady,
+    ConsentRequired,
+    ContextNotFound,
+    Expired,
+    /// The context identity has no registry principal to make the call as
+    UnmappedIdentity(String),
+    Transport(String),
+}
+
+impl RegistryError {
+    /// Only transport failures are retried; the canister rejects the same call the same way
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Transport(_))
+    }
+
+    /// Whether this is the canister's `UpdateConflict` with the given reason
+    pub fn is_conflict(&self, reason: &str) -> bool {
+        matches!(self, Self::UpdateConflict(r) if r == reason)
+    }
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInput(reason) => write!(f, "Invalid input: {}", reason),
+            Self::NotFound => write!(f, "Not found"),
+            Self::AlreadyExists => write!(f, "Already exists"),
+            Self::UpdateConflict(reason) => write!(f, "Update conflict: {}", reason),
+            Self::Unauthorized => write!(f, "Unauthorized"),
+            Self::DocumentNotReady => write!(f, "Document not ready"),
+            Self::ConsentRequired => write!(f, "Consent required"),
+            Self::ContextNotFound => write!(f, "Context not found"),
+            Self::Expired => write!(f, "Signing deadline has passed"),
+            Self::UnmappedIdentity(identity) => {
+                write!(f, "No registry principal for context identity {}", identity)
+            }
+            Self::Transport(reason) => write!(f, "Registry unreachable: {}", reason),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::time::Duration;
+
+use merodocs_relay::{ContextEvent, LocalRegistry, RegistryError, Relay, RelayEvent, RetryPolicy};
+
+const RELAY: &str = "relay-identity";
+const CONTEXT: &str = "context-1";
+const DOCUMENT: &str = "document-1";
+const SIGNER: &str = "signer-1";
+const SIGNER_PRINCIPAL: &str = "signer-1-principal";
+const COSIGNER: &str = "signer-2";
+const COSIGNER_PRINCIPAL: &str = "signer-2-principal";
+
+fn relay(registry: LocalRegistry) -> Relay<LocalRegistry> {
+    Relay::new(registry, RELAY)
+        .with_principal(SIGNER, SIGNER_PRINCIPAL)
+        .with_principal(COSIGNER, COSIGNER_PRINCIPAL)
+        .with_retry_policy(RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        })
+}
+
+fn event(sequence: u64, event: RelayEvent) -> ContextEvent {
+    ContextEvent {
+        context_id: CONTEXT.to_string(),
+        sequence,
+        event,
+    }
+}
+
+fn uploaded() -> ContextEvent {
+    event(
+        0,
+        RelayEvent::DocumentUploaded {
+            id: DOCUMENT.to_string(),
+            hash: "original-hash".to_string(),
+            uploaded_by: RELAY.to_string(),
+        },
+    )
+}
+
+fn consent_given(sequence: u64) -> ContextEvent {
+    consent_given_by(sequence, SIGNER)
+}
+
+fn consent_given_by(sequence: u64, user_id: &str) -> ContextEvent {
+    event(
+        sequence,
+        RelayEvent::ConsentGiven {
+            document_id: DOCUMENT.to_string(),
+            user_id: user_id.to_string(),
+            disclosure_hash: None,
+        },
+    )
+}
+
+fn consent_withdrawn(sequence: u64) -> ContextEvent {
+    event(
+        sequence,
+        RelayEvent::ConsentWithdrawn {
+            document_id: DOCUMENT.to_string(),
+            user_id: SIGNER.to_string(),
+        },
+    )
+}
+
+fn signed_by(sequence: u64, signer: &str) -> ContextEvent {
+    event(
+        sequence,
+        RelayEvent::DocumentSigned {
+            document_id: DOCUMENT.to_string(),
+            signer: signer.to_string(),
+        },
+    )
+}
+
+fn signing_session() -> Vec<ContextEvent> {
+    vec![
+        uploaded(),
+        consent_given(1),
+        signed_by(2, SIGNER),
+        event(
+            3,
+            RelayEvent::DocumentCompleted {
+                document_id: DOCUMENT.to_string(),
+                final_hash: "final-hash".to_string(),
+            },
+        ),
+    ]
+}
+
+#[test]
+fn transport_failures_are_retried() {
+    let mut relay = relay(LocalRegistry::new());
+    relay.registry_mut().fail_next(2);
+
+    relay.handle(&uploaded()).unwrap();
+
+    let document = relay.registry().document(DOCUMENT).unwrap();
+    assert_eq!(document.context_id, CONTEXT);
+    assert_eq!(relay.registry().context(CONTEXT).unwrap().admin_id, RELAY);
+}
+
+#[test]
+fn exhausted_retries_are_reported_and_resumed() {
+    let mut relay = relay(LocalRegistry::new());
+    relay.registry_mut().fail_next(3);
+
+    let error = relay.handle(&uploaded()).unwrap_err();
+    assert_eq!(error.idempotency_key, format!("context:{}", CONTEXT));
+    assert_eq!(error.attempts, 3);
+    assert!(matches!(error.error, RegistryError::Transport(_)));
+    assert!(relay.registry().document(DOCUMENT).is_none());
+
+    relay.handle(&uploaded()).unwrap();
+    assert!(relay.registry().document(DOCUMENT).is_some());
+}
+
+#[test]
+fn replayed_events_are_applied_once() {
+    let mut relay = relay(LocalRegistry::new());
+    for event in signing_session() {
+        relay.handle(&event).unwrap();
+    }
+    let mut delivered: Vec<String> = relay.delivered().map(str::to_string).collect();
+
+    // Same relay: every call is skipped by its idempotency key
+    for event in signing_session() {
+        relay.handle(&event).unwrap();
+    }
+
+    // Fresh relay without the delivered keys: the registry reports each call as a duplicate
+    let mut restarted = self::relay(relay.into_registry());
+    for event in signing_session() {
+        restarted.handle(&event).unwrap();
+    }
+
+    let document = restarted.registry().document(DOCUMENT).unwrap();
+    assert_eq!(document.current_signers, vec![SIGNER_PRINCIPAL.to_string()]);
+    assert!(document.fully_signed);
+    assert_eq!(document.final_hash.as_deref(), Some("final-hash"));
+    assert_eq!(
+        restarted.registry().context(CONTEXT).unwrap().participants,
+        vec![SIGNER_PRINCIPAL.to_string()]
+    );
+
+    let mut resumed: Vec<String> = restarted.delivered().map(str::to_string).collect();
+    resumed.sort();
+    delivered.sort();
+    assert_eq!(resumed, delivered);
+}
+
+#[test]
+fn re_consent_supersedes_withdrawal() {
+    let mut relay = relay(LocalRegistry::new());
+    relay.handle(&uploaded()).unwrap();
+
+    relay.handle(&consent_given(1)).unwrap();
+    assert!(relay.registry().has_consented(SIGNER_PRINCIPAL, DOCUMENT));
+
+    relay.handle(&consent_withdrawn(2)).unwrap();
+    assert!(!relay.registry().has_consented(SIGNER_PRINCIPAL, DOCUMENT));
+
+    relay.handle(&consent_given(3)).unwrap();
+    assert!(relay.registry().has_consented(SIGNER_PRINCIPAL, DOCUMENT));
+
+    relay.handle(&consent_withdrawn(4)).unwrap();
+    assert!(!relay.registry().has_consented(SIGNER_PRINCIPAL, DOCUMENT));
+}
+
+#[test]
+fn replayed_consent_history_keeps_the_latest_decision() {
+    let history = vec![
+        uploaded(),
+        consent_given(1),
+        consent_withdrawn(2),
+        consent_given(3),
+    ];
+
+    let mut relay = relay(LocalRegistry::new());
+    for _ in 0..2 {
+        for event in &history {
+            relay.handle(event).unwrap();
+        }
+        assert!(relay.registry().has_consented(SIGNER_PRINCIPAL, DOCUMENT));
+    }
+    let delivered = relay.delivered().count();
+
+    // A restarted relay re-submits every toggle, in order, and ends on the same decision
+    let mut restarted = self::relay(relay.into_registry());
+    for _ in 0..2 {
+        for event in &history {
+            restarted.handle(event).unwrap();
+        }
+        assert!(restarted
+            .registry()
+            .has_consented(SIGNER_PRINCIPAL, DOCUMENT));
+    }
+    assert_eq!(restarted.delivered().count(), delivered);
+}
+
+#[test]
+fn withdrawing_an_unrecorded_consent_is_reported() {
+    let mut relay = relay(LocalRegistry::new());
+    relay.handle(&uploaded()).unwrap();
+    relay
+        .handle(&event(
+            1,
+            RelayEvent::ParticipantJoined {
+                user_id: SIGNER.to_string(),
+            },
+        ))
+        .unwrap();
+
+    let error = relay.handle(&consent_withdrawn(2)).unwrap_err();
+    assert_eq!(error.attempts, 1);
+    assert_eq!(
+        error.error,
+        RegistryError::UpdateConflict("User has not consented to this document.".to_string())
+    );
+    assert!(!relay.delivered().any(|key| key.starts_with("withdraw:")));
+}
+
+#[test]
+fn expired_documents_refuse_signatures_without_retrying() {
+    let mut relay = relay(LocalRegistry::new());
+    relay.handle(&uploaded()).unwrap();
+    relay.handle(&consent_given(1)).unwrap();
+    relay.registry_mut().expire_document(DOCUMENT);
+
+    let error = relay.handle(&signed_by(2, SIGNER)).unwrap_err();
+    assert_eq!(error.error, RegistryError::Expired);
+    assert_eq!(error.attempts, 1);
+    assert!(relay
+        .registry()
+        .document(DOCUMENT)
+        .unwrap()
+        .current_signers
+        .is_empty());
+}
+
+#[test]
+fn documents_are_fully_signed_once_every_participant_signs() {
+    let mut relay = relay(LocalRegistry::new());
+    relay.handle(&uploaded()).unwrap();
+    relay.handle(&consent_given_by(1, SIGNER)).unwrap();
+    relay.handle(&consent_given_by(2, COSIGNER)).unwrap();
+
+    let context = relay.registry().context(CONTEXT).unwrap();
+    assert_eq!(context.admin_id, RELAY);
+    assert!(!context.admin_signs);
+
+    relay.handle(&signed_by(3, SIGNER)).unwrap();
+    assert!(!relay.registry().document(DOCUMENT).unwrap().fully_signed);
+
+    relay.handle(&signed_by(4, COSIGNER)).unwrap();
+    let document = relay.registry().document(DOCUMENT).unwrap();
+    assert_eq!(
+        document.current_signers,
+        vec![SIGNER_PRINCIPAL.to_string(), COSIGNER_PRINCIPAL.to_string()]
+    );
+    assert!(document.fully_signed);
+}
+
+#[test]
+fn identities_without_a_principal_are_reported() {
+    let mut relay = relay(LocalRegistry::new());
+    relay.handle(&uploaded()).unwrap();
+
+    let error = relay.handle(&consent_given_by(1, "stranger")).unwrap_err();
+    assert_eq!(
+        error.error,
+        RegistryError::UnmappedIdentity("stranger".to_string())
+    );
+    assert!(relay
+        .registry()
+        .context(CONTEXT)
+        .unwrap()
+        .participants
+        .is_empty());
+    assert!(!relay.registry().has_consented("stranger", DOCUMENT));
+}